@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS quote_decks
+(
+    "user_id" BIGINT PRIMARY KEY NOT NULL,
+    "champion" VARCHAR(32) NOT NULL,
+    "seed" BIGINT NOT NULL,
+    "position" INTEGER NOT NULL
+);
@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS category_weights
+(
+    "guild_id" BIGINT NOT NULL,
+    "category" VARCHAR(16) NOT NULL,
+    "weight" INTEGER NOT NULL,
+    PRIMARY KEY ("guild_id", "category")
+);
@@ -0,0 +1,7 @@
+ALTER TABLE users ADD COLUMN "audio" BOOLEAN;
+
+CREATE TABLE IF NOT EXISTS guild_settings
+(
+    "guild_id" BIGINT PRIMARY KEY NOT NULL,
+    "audio" BOOLEAN NOT NULL DEFAULT 0
+);
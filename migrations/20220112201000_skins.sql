@@ -0,0 +1 @@
+ALTER TABLE users ADD COLUMN "skin" VARCHAR(64);
@@ -0,0 +1,2 @@
+ALTER TABLE users ADD COLUMN "chaos" BOOLEAN NOT NULL DEFAULT 0;
+ALTER TABLE users ADD COLUMN "chaos_tags" VARCHAR(128);
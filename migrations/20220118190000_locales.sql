@@ -0,0 +1,3 @@
+ALTER TABLE users ADD COLUMN "locale" VARCHAR(8);
+
+ALTER TABLE guild_settings ADD COLUMN "locale" VARCHAR(8);
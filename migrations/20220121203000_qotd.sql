@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS qotd_subscriptions
+(
+    "channel_id" BIGINT PRIMARY KEY NOT NULL,
+    "guild_id" BIGINT NOT NULL,
+    "minute" INTEGER NOT NULL,
+    "last_posted" BIGINT
+);
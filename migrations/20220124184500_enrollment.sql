@@ -0,0 +1,6 @@
+ALTER TABLE users ADD COLUMN "opted_in" BOOLEAN;
+
+-- Everyone with settings so far was being quoted, keep quoting them.
+UPDATE users SET opted_in=1;
+
+ALTER TABLE guild_settings ADD COLUMN "auto_enroll" BOOLEAN NOT NULL DEFAULT 0;
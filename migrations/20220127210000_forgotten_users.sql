@@ -0,0 +1,4 @@
+CREATE TABLE IF NOT EXISTS forgotten_users
+(
+    "user_id" BIGINT PRIMARY KEY NOT NULL
+);
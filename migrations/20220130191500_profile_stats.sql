@@ -0,0 +1,3 @@
+ALTER TABLE users ADD COLUMN "picked_at" BIGINT;
+
+ALTER TABLE users ADD COLUMN "quote_count" INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS rate_overrides
+(
+    "user_id" BIGINT NOT NULL,
+    "scope" VARCHAR(8) NOT NULL,
+    "scope_id" BIGINT NOT NULL,
+    "rate" INTEGER NOT NULL,
+    PRIMARY KEY ("user_id", "scope_id")
+);
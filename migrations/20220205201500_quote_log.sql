@@ -0,0 +1,16 @@
+CREATE TABLE IF NOT EXISTS quote_log
+(
+    "id" INTEGER PRIMARY KEY AUTOINCREMENT,
+    "guild_id" BIGINT,
+    "channel_id" BIGINT NOT NULL,
+    "user_id" BIGINT NOT NULL,
+    "champion" VARCHAR(32) NOT NULL,
+    "voice" VARCHAR(128) NOT NULL,
+    "quote" TEXT NOT NULL,
+    "trigger_type" VARCHAR(8) NOT NULL,
+    "timestamp" BIGINT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS quote_log_user_id ON quote_log ("user_id", "timestamp");
+
+CREATE INDEX IF NOT EXISTS quote_log_guild_id ON quote_log ("guild_id", "timestamp");
@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS guild_members
+(
+    "guild_id" BIGINT NOT NULL,
+    "user_id" BIGINT NOT NULL,
+    PRIMARY KEY ("guild_id", "user_id")
+);
+
+CREATE INDEX IF NOT EXISTS guild_members_user_id ON guild_members ("user_id");
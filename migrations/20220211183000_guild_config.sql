@@ -0,0 +1,16 @@
+ALTER TABLE guild_settings ADD COLUMN "enabled" BOOLEAN NOT NULL DEFAULT 1;
+
+ALTER TABLE guild_settings ADD COLUMN "default_rate" INTEGER;
+
+ALTER TABLE users ADD COLUMN "rate_set" BOOLEAN NOT NULL DEFAULT 0;
+
+-- Everyone got a rate of 10 until they changed it.
+UPDATE users SET rate_set=1 WHERE rate<>10;
+
+CREATE TABLE IF NOT EXISTS ignored_channels
+(
+    "channel_id" BIGINT PRIMARY KEY NOT NULL,
+    "guild_id" BIGINT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS ignored_channels_guild_id ON ignored_channels ("guild_id");
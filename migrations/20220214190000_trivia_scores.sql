@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS trivia_scores
+(
+    "guild_id" BIGINT NOT NULL,
+    "user_id" BIGINT NOT NULL,
+    "points" INTEGER NOT NULL,
+    PRIMARY KEY ("guild_id", "user_id")
+);
@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS duel_records
+(
+    "user_id" BIGINT PRIMARY KEY NOT NULL,
+    "wins" INTEGER NOT NULL DEFAULT 0,
+    "losses" INTEGER NOT NULL DEFAULT 0,
+    "draws" INTEGER NOT NULL DEFAULT 0
+);
@@ -0,0 +1,3 @@
+-- Decks laid out for different weights can't be continued, existing decks
+-- get a layout no draw matches and are replaced on their next draw.
+ALTER TABLE quote_decks ADD COLUMN "layout" BIGINT NOT NULL DEFAULT 0;
@@ -0,0 +1,2 @@
+-- Unsubscribing keeps the row so the last posted day survives a resubscribe.
+ALTER TABLE qotd_subscriptions ADD COLUMN "active" BOOLEAN NOT NULL DEFAULT 1;
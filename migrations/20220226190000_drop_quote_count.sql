@@ -0,0 +1,2 @@
+-- Times quoted are counted from quote_log instead.
+ALTER TABLE users DROP COLUMN "quote_count";
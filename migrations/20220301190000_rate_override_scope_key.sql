@@ -0,0 +1,15 @@
+-- A server's ID can equal its first channel's, so the scope is part of the key.
+CREATE TABLE rate_overrides_new
+(
+    "user_id" BIGINT NOT NULL,
+    "scope" VARCHAR(8) NOT NULL,
+    "scope_id" BIGINT NOT NULL,
+    "rate" INTEGER NOT NULL,
+    PRIMARY KEY ("user_id", "scope", "scope_id")
+);
+
+INSERT INTO rate_overrides_new (user_id, scope, scope_id, rate) SELECT user_id, scope, scope_id, rate FROM rate_overrides;
+
+DROP TABLE rate_overrides;
+
+ALTER TABLE rate_overrides_new RENAME TO rate_overrides;
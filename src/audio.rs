@@ -0,0 +1,188 @@
+use dashmap::DashMap;
+use hyper::{
+    body,
+    client::HttpConnector,
+    header::{HeaderValue, LOCATION},
+    Body, Client, Method, Request, Uri,
+};
+use hyper_rustls::{HttpsConnector, HttpsConnectorBuilder};
+use tokio::{fs, sync::Mutex};
+
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use crate::{ddragon::Locale, Error};
+
+const MAX_REDIRECTS: usize = 5;
+
+/// On-disk cache of voice-line clips from the wiki, so each file is only
+/// downloaded once.
+pub struct AudioCache {
+    client: Client<HttpsConnector<HttpConnector>>,
+    directory: PathBuf,
+    downloads: DashMap<PathBuf, Arc<Mutex<()>>>,
+}
+
+/// Maps a wiki file name to a cache file name of safe characters only.
+/// Names that had to be changed are prefixed with a hash of the original, so
+/// distinct files never share a cache entry.
+fn cache_name(file: &str) -> String {
+    // MediaWiki treats spaces and underscores the same.
+    let file = file.replace(' ', "_");
+
+    let name: String = file
+        .trim_start_matches('.')
+        .chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '.' | '-' | '_' => c,
+            _ => '_',
+        })
+        .collect();
+
+    if name == file {
+        return name;
+    }
+
+    let hash = file.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+
+    format!("{:016x}-{}", hash, name)
+}
+
+impl AudioCache {
+    pub async fn new(directory: PathBuf) -> Result<Self, Error> {
+        fs::create_dir_all(&directory).await?;
+
+        let connector = HttpsConnectorBuilder::new()
+            .with_webpki_roots()
+            .https_only()
+            .enable_http1()
+            .enable_http2()
+            .build();
+
+        Ok(Self {
+            client: Client::builder().build(connector),
+            directory,
+            downloads: DashMap::new(),
+        })
+    }
+
+    pub async fn get(&self, locale: Locale, file: &str) -> Result<Vec<u8>, Error> {
+        // Each wiki hosts its own files, which may share names across languages.
+        let directory = match locale {
+            Locale::English => self.directory.clone(),
+            _ => self.directory.join(locale.code()),
+        };
+
+        let name = cache_name(file);
+        let path = directory.join(&name);
+
+        if let Ok(data) = fs::read(&path).await {
+            return Ok(data);
+        }
+
+        // Concurrent misses for the same file wait for the first download.
+        let lock = self.downloads.entry(path.clone()).or_default().clone();
+
+        let result = {
+            let _guard = lock.lock().await;
+
+            match fs::read(&path).await {
+                Ok(data) => Ok(data),
+                Err(_) => self.fetch(locale, &directory, &name, file).await,
+            }
+        };
+
+        self.downloads.remove(&path);
+
+        result
+    }
+
+    async fn fetch(
+        &self,
+        locale: Locale,
+        directory: &Path,
+        name: &str,
+        file: &str,
+    ) -> Result<Vec<u8>, Error> {
+        let data = self.download(locale.wiki_url(), file).await?;
+        fs::create_dir_all(directory).await?;
+
+        // Move the file into place once it's complete, so a crash can't leave
+        // a truncated clip in the cache.
+        let partial = directory.join(format!("{}.part", name));
+        fs::write(&partial, &data).await?;
+        fs::rename(&partial, directory.join(name)).await?;
+
+        Ok(data)
+    }
+
+    async fn download(&self, wiki_url: &str, file: &str) -> Result<Vec<u8>, Error> {
+        let mut uri: Uri = format!("{}Special:FilePath/{}", wiki_url, file.replace(' ', "_"))
+            .parse()
+            .map_err(hyper::http::Error::from)?;
+
+        for _ in 0..MAX_REDIRECTS {
+            let req = Request::builder()
+                .method(Method::GET)
+                .uri(uri.clone())
+                .body(Body::empty())?;
+
+            let res = self.client.request(req).await?;
+
+            if res.status().is_redirection() {
+                uri = match res.headers().get(LOCATION).map(HeaderValue::to_str) {
+                    Some(Ok(location)) => location.parse().map_err(hyper::http::Error::from)?,
+                    _ => return Err(Error::UnexpectedStatus(res.status())),
+                };
+
+                continue;
+            }
+
+            if !res.status().is_success() {
+                return Err(Error::UnexpectedStatus(res.status()));
+            }
+
+            return Ok(body::to_bytes(res).await?.to_vec());
+        }
+
+        Err(Error::UnexpectedStatus(hyper::StatusCode::LOOP_DETECTED))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::cache_name;
+
+    #[test]
+    fn keeps_plain_names() {
+        assert_eq!(
+            cache_name("Ahri.Original.Move1.ogg"),
+            "Ahri.Original.Move1.ogg"
+        );
+        assert_eq!(cache_name("Ahri Move 1.ogg"), "Ahri_Move_1.ogg");
+    }
+
+    #[test]
+    fn never_escapes_the_cache() {
+        for file in [
+            "../../etc/passwd",
+            "..",
+            "a/b.ogg",
+            "a\\b.ogg",
+            "Kai'Sa.ogg",
+        ] {
+            let name = cache_name(file);
+
+            assert!(!name.starts_with('.'));
+            assert!(name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_')));
+        }
+
+        assert_ne!(cache_name("a/b.ogg"), cache_name("a\\b.ogg"));
+    }
+}
@@ -4,10 +4,12 @@ use hyper::{
     Body, Client, Method, Request,
 };
 use hyper_rustls::{HttpsConnector, HttpsConnectorBuilder};
-use lol_quotes::ddragon::{ChampionData, ChampionExport};
+use lol_quotes::ddragon::{ChampionData, ChampionDetailData, ChampionExport, Locale, SkinExport};
 use tokio::fs;
 use tracing::{error, info};
 
+use std::collections::HashMap;
+
 type HyperClient = Client<HttpsConnector<HttpConnector>>;
 
 const VERSIONS_ENDPOINT: &str = "https://ddragon.leagueoflegends.com/api/versions.json";
@@ -28,12 +30,34 @@ async fn get_versions(client: &HyperClient) -> Result<Vec<String>, lol_quotes::E
 async fn get_champions(
     client: &HyperClient,
     version: &str,
+    locale: Locale,
 ) -> Result<ChampionData, lol_quotes::Error> {
     let req = Request::builder()
         .method(Method::GET)
         .uri(format!(
-            "https://ddragon.leagueoflegends.com/cdn/{}/data/en_US/champion.json",
-            version
+            "https://ddragon.leagueoflegends.com/cdn/{}/data/{}/champion.json",
+            version,
+            locale.code()
+        ))
+        .body(Body::empty())?;
+
+    let res = client.request(req).await?;
+
+    let body = body::aggregate(res).await?;
+
+    Ok(simd_json::from_reader(body.reader())?)
+}
+
+async fn get_champion_detail(
+    client: &HyperClient,
+    version: &str,
+    id: &str,
+) -> Result<ChampionDetailData, lol_quotes::Error> {
+    let req = Request::builder()
+        .method(Method::GET)
+        .uri(format!(
+            "https://ddragon.leagueoflegends.com/cdn/{}/data/en_US/champion/{}.json",
+            version, id
         ))
         .body(Body::empty())?;
 
@@ -66,7 +90,7 @@ async fn main() {
         }
     };
 
-    let latest_version = match versions.get(0) {
+    let latest_version = match versions.first() {
         Some(version) => version,
         None => {
             error!("No versions found");
@@ -76,7 +100,7 @@ async fn main() {
 
     info!("Latest game version is {}", latest_version);
 
-    let champions = match get_champions(&client, latest_version).await {
+    let champions = match get_champions(&client, latest_version, Locale::English).await {
         Ok(res) => res,
         Err(e) => {
             error!("Failed to get champions: {:?}", e);
@@ -84,20 +108,82 @@ async fn main() {
         }
     };
 
+    // Champion ID -> locale code -> localized name
+    let mut localized_names: HashMap<String, HashMap<String, String>> = HashMap::new();
+
+    for locale in Locale::ALL {
+        if locale == Locale::English {
+            continue;
+        }
+
+        info!("Getting {} champion names", locale.code());
+
+        let localized = match get_champions(&client, latest_version, locale).await {
+            Ok(res) => res,
+            Err(e) => {
+                error!("Failed to get {} champions: {:?}", locale.code(), e);
+                return;
+            }
+        };
+
+        for (id, champion) in localized.data {
+            localized_names
+                .entry(id)
+                .or_default()
+                .insert(locale.code().to_string(), champion.name);
+        }
+    }
+
     info!("Found {} champions, exporting", champions.data.len());
 
-    let export_champions: Vec<ChampionExport> = champions
-        .data
-        .into_iter()
-        .map(|(_, champion)| ChampionExport {
+    let mut export_champions: Vec<ChampionExport> = Vec::with_capacity(champions.data.len());
+
+    for (_, champion) in champions.data {
+        info!("Getting skins for {}", champion.name);
+
+        let detail = match get_champion_detail(&client, latest_version, &champion.id).await {
+            Ok(res) => res,
+            Err(e) => {
+                error!("Failed to get details for {}: {:?}", champion.name, e);
+                return;
+            }
+        };
+
+        let skins = detail
+            .data
+            .into_values()
+            .flat_map(|detail| detail.skins)
+            .filter(|skin| skin.num != 0)
+            .map(|skin| SkinExport {
+                icon: format!(
+                    "https://ddragon.leagueoflegends.com/cdn/img/champion/tiles/{}_{}.jpg",
+                    champion.id, skin.num
+                ),
+                splash: format!(
+                    "https://ddragon.leagueoflegends.com/cdn/img/champion/splash/{}_{}.jpg",
+                    champion.id, skin.num
+                ),
+                name: skin.name,
+            })
+            .collect();
+
+        export_champions.push(ChampionExport {
             name: champion.name,
-            id: champion.id,
             icon: format!(
                 "https://ddragon.leagueoflegends.com/cdn/{}/img/champion/{}",
                 latest_version, champion.image.full
             ),
-        })
-        .collect();
+            names: localized_names.remove(&champion.id).unwrap_or_default(),
+            id: champion.id,
+            title: champion.title,
+            blurb: champion.blurb,
+            partype: champion.partype,
+            info: Some(champion.info),
+            stats: Some(champion.stats),
+            tags: champion.tags,
+            skins,
+        });
+    }
 
     info!("Writing results to champions.json");
 
@@ -1,18 +1,29 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use hyper::{body, client::HttpConnector, Body, Client, Method, Request};
 use hyper_rustls::{HttpsConnector, HttpsConnectorBuilder};
-use lol_quotes::ddragon::{ChampionExport, QuoteExport};
+use lol_quotes::ddragon::{
+    ChampionExport, Locale, Quote, QuoteCategory, QuoteExport, SkinQuoteExport,
+};
 use regex::{Captures, RegexBuilder};
 use tokio::fs;
 use tracing::{error, info};
 
 type HyperClient = Client<HttpsConnector<HttpConnector>>;
 
-async fn get_quotes(client: &HyperClient, name: &str) -> Result<Vec<String>, lol_quotes::Error> {
+async fn get_quotes(
+    client: &HyperClient,
+    wiki_url: &str,
+    audio_page: &str,
+    name: &str,
+    champion_names: &[String],
+    skin_names: &[String],
+) -> Result<Vec<(Option<String>, Quote)>, lol_quotes::Error> {
     let wiki_url = format!(
-        "https://leagueoflegends.fandom.com/wiki/{}/LoL/Audio?action=raw",
-        name.replace(" ", "_")
+        "{}{}/{}?action=raw",
+        wiki_url,
+        name.replace(" ", "_"),
+        audio_page
     );
 
     let req = Request::builder()
@@ -25,10 +36,22 @@ async fn get_quotes(client: &HyperClient, name: &str) -> Result<Vec<String>, lol
     let body = body::to_bytes(res).await?;
     let text = String::from_utf8(body.to_vec()).unwrap_or_default();
 
-    Ok(parse_quotes(text, name))
+    Ok(parse_quotes(text, name, champion_names, skin_names))
 }
 
-fn parse_quotes(text: String, name: &str) -> Vec<String> {
+struct Section {
+    level: usize,
+    category: QuoteCategory,
+    target: Option<String>,
+    skin: Option<String>,
+}
+
+fn parse_quotes(
+    text: String,
+    name: &str,
+    champion_names: &[String],
+    skin_names: &[String],
+) -> Vec<(Option<String>, Quote)> {
     let small_bold_caps_regex = RegexBuilder::new(r"\{\{sbc\|([^}]+)\}\}")
         .multi_line(true)
         .build()
@@ -178,29 +201,102 @@ fn parse_quotes(text: String, name: &str) -> Vec<String> {
             .to_string()
     });
 
-    let mut matches = Vec::new();
-
-    if name != "Kindred" {
-        let re = RegexBuilder::new(r#"''"(.*)"''"#)
-            .multi_line(true)
+    let heading_regex =
+        RegexBuilder::new(r"^(?:(?P<level>=+)\s*(?P<heading>.+?)\s*=+|;\s*(?P<term>.+))$")
             .build()
             .unwrap();
-        for captures in re.captures_iter(&text) {
-            let capture = &captures[1];
 
-            if !capture.contains("ogg") && capture != "GG!" {
-                let text = capture.replace("'''", "**");
-                matches.push(text);
-            }
+    let quote_regex = RegexBuilder::new(r#"''"(.*)"''"#).build().unwrap();
+
+    let audio_regex = RegexBuilder::new(r"\{\{sm2\|(?P<file>[^}|]+\.ogg)")
+        .build()
+        .unwrap();
+
+    let kindred_regex = RegexBuilder::new(r#"(Wolf|Lamb|Kindred): ''(?:")?([^"]+)?(?:")?''"#)
+        .build()
+        .unwrap();
+
+    let mut matches: Vec<(Option<String>, Quote)> = Vec::new();
+    let mut sections: Vec<Section> = Vec::new();
+    let mut last_asterisk_count = 1;
+
+    for line in text.lines() {
+        let cleaned = line.trim();
+
+        if let Some(captures) = heading_regex.captures(cleaned) {
+            // Definition terms (";Taunt") nest below every wiki heading level.
+            let (level, heading) = match captures.name("level") {
+                Some(level) => (level.as_str().len(), &captures["heading"]),
+                None => (usize::MAX, &captures["term"]),
+            };
+
+            let category = categorize(heading);
+
+            // Skin names often contain the champion's own name, so a skin
+            // heading must never be read as an interaction target.
+            let skin = match category {
+                QuoteCategory::Other => find_skin(heading, skin_names),
+                _ => None,
+            };
+            let target = match skin {
+                Some(_) => None,
+                None => find_target(heading, champion_names),
+            };
+
+            sections.retain(|section| section.level < level);
+            sections.push(Section {
+                level,
+                category,
+                target,
+                skin,
+            });
+
+            continue;
         }
-    } else {
-        let re = RegexBuilder::new(r#"(Wolf|Lamb|Kindred): ''(?:")?([^"]+)?(?:")?''"#)
-            .build()
-            .unwrap();
-        let mut last_asterisk_count = 1;
 
-        for line in text.lines() {
-            let cleaned = line.trim();
+        let category = sections
+            .iter()
+            .rev()
+            .map(|section| section.category)
+            .find(|category| *category != QuoteCategory::Other)
+            .unwrap_or_default();
+
+        let section_target = sections
+            .iter()
+            .rev()
+            .find_map(|section| section.target.clone());
+
+        let skin = sections
+            .iter()
+            .rev()
+            .find_map(|section| section.skin.clone());
+
+        if name != "Kindred" {
+            for captures in quote_regex.captures_iter(cleaned) {
+                let capture = &captures[1];
+
+                if !capture.contains("ogg") && capture != "GG!" {
+                    let text = capture.replace("'''", "**");
+                    let prefix = &cleaned[..captures.get(0).unwrap().start()];
+                    let target =
+                        find_target(prefix, champion_names).or_else(|| section_target.clone());
+                    let audio = audio_regex
+                        .captures_iter(prefix)
+                        .last()
+                        .map(|audio| audio["file"].trim().to_string());
+
+                    matches.push((
+                        skin.clone(),
+                        Quote {
+                            text,
+                            category,
+                            target,
+                            audio,
+                        },
+                    ));
+                }
+            }
+        } else {
             let mut asterisk_count_this_line = 0;
 
             for letter in cleaned.chars() {
@@ -211,15 +307,29 @@ fn parse_quotes(text: String, name: &str) -> Vec<String> {
                 }
             }
 
-            for capture in re.captures_iter(&cleaned) {
+            for capture in kindred_regex.captures_iter(cleaned) {
                 let quote = format!("{}: {}", &capture[1], &capture[2]).replace("'''", "**");
 
                 if asterisk_count_this_line == last_asterisk_count + 1 {
                     let idx = matches.len() - 1;
-                    matches[idx].push_str("\n");
-                    matches[idx].push_str(&quote);
+                    matches[idx].1.text.push('\n');
+                    matches[idx].1.text.push_str(&quote);
                 } else {
-                    matches.push(quote);
+                    let prefix = &cleaned[..capture.get(0).unwrap().start()];
+                    let audio = audio_regex
+                        .captures_iter(prefix)
+                        .last()
+                        .map(|audio| audio["file"].trim().to_string());
+
+                    matches.push((
+                        skin.clone(),
+                        Quote {
+                            text: quote,
+                            category,
+                            target: section_target.clone(),
+                            audio,
+                        },
+                    ));
                 }
 
                 last_asterisk_count = asterisk_count_this_line;
@@ -230,6 +340,207 @@ fn parse_quotes(text: String, name: &str) -> Vec<String> {
     matches
 }
 
+fn find_target(text: &str, champion_names: &[String]) -> Option<String> {
+    champion_names
+        .iter()
+        .filter(|name| {
+            text.match_indices(name.as_str()).any(|(idx, _)| {
+                let before = text[..idx].chars().next_back();
+                let after = text[idx + name.len()..].chars().next();
+
+                !before.is_some_and(char::is_alphanumeric)
+                    && !after.is_some_and(char::is_alphanumeric)
+            })
+        })
+        .max_by_key(|name| name.len())
+        .cloned()
+}
+
+fn normalize(text: &str) -> String {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Finds the skin a heading names. Only exact names count, since headings
+/// like the champion's own name are part of many skin names.
+fn find_skin(heading: &str, skin_names: &[String]) -> Option<String> {
+    let heading = normalize(heading);
+
+    if heading.is_empty() {
+        return None;
+    }
+
+    skin_names
+        .iter()
+        .find(|skin| normalize(skin) == heading)
+        .cloned()
+}
+
+fn categorize(heading: &str) -> QuoteCategory {
+    let heading = heading.to_lowercase();
+
+    let keywords = [
+        ("recall", QuoteCategory::Recall),
+        ("pick", QuoteCategory::Pick),
+        ("ban", QuoteCategory::Ban),
+        ("movement", QuoteCategory::Movement),
+        ("moving", QuoteCategory::Movement),
+        ("attack", QuoteCategory::Attack),
+        ("taunt", QuoteCategory::Taunt),
+        ("joke", QuoteCategory::Joke),
+        ("laugh", QuoteCategory::Laugh),
+        ("kill", QuoteCategory::Kill),
+        ("death", QuoteCategory::Death),
+        ("dying", QuoteCategory::Death),
+        ("upon using", QuoteCategory::Ability),
+        ("casting", QuoteCategory::Ability),
+        // German wiki headings
+        ("rückruf", QuoteCategory::Recall),
+        ("auswahl", QuoteCategory::Pick),
+        ("bann", QuoteCategory::Ban),
+        ("bewegung", QuoteCategory::Movement),
+        ("angriff", QuoteCategory::Attack),
+        ("spott", QuoteCategory::Taunt),
+        ("witz", QuoteCategory::Joke),
+        ("lachen", QuoteCategory::Laugh),
+        ("töten", QuoteCategory::Kill),
+        ("tod", QuoteCategory::Death),
+        ("fähigkeit", QuoteCategory::Ability),
+    ];
+
+    keywords
+        .into_iter()
+        .find(|(keyword, _)| heading.contains(keyword))
+        .map(|(_, category)| category)
+        .unwrap_or_default()
+}
+
+async fn get_locale_quotes(
+    client: &HyperClient,
+    locale: Locale,
+    wiki_url: &str,
+    champions: &[ChampionExport],
+) -> Result<HashMap<String, QuoteExport>, lol_quotes::Error> {
+    let localized_name = |champion: &ChampionExport| {
+        champion
+            .names
+            .get(locale.code())
+            .unwrap_or(&champion.name)
+            .clone()
+    };
+
+    let champion_count = champions.len();
+    let champion_names: Vec<String> = champions.iter().map(localized_name).collect();
+    // Interaction targets are always stored under the English name.
+    let english_names: HashMap<String, String> = champions
+        .iter()
+        .map(|champion| (localized_name(champion), champion.name.clone()))
+        .collect();
+    let mut champion_quote_data = HashMap::with_capacity(champion_count);
+
+    for (idx, champion) in champions.iter().enumerate() {
+        let name = localized_name(champion);
+
+        info!(
+            "Getting {} quotes for {} ({}/{})",
+            locale.code(),
+            name,
+            idx + 1,
+            champion_count
+        );
+
+        let name_to_use = if name.contains("&") {
+            &champion.id
+        } else {
+            &name
+        };
+
+        // Localized wikis name skins in their own language, which we don't
+        // have, so their skin lines are kept with the champion's.
+        let skin_names: Vec<String> = match locale {
+            Locale::English => champion
+                .skins
+                .iter()
+                .map(|skin| skin.name.clone())
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        let parsed = get_quotes(
+            client,
+            wiki_url,
+            locale.audio_page(),
+            name_to_use,
+            &champion_names,
+            &skin_names,
+        )
+        .await?;
+
+        let mut quotes = Vec::new();
+        let mut skin_quotes: HashMap<String, Vec<Quote>> = HashMap::new();
+
+        for (skin, mut quote) in parsed {
+            quote.target = quote
+                .target
+                .and_then(|target| english_names.get(&target).cloned());
+
+            match skin {
+                Some(skin) => skin_quotes.entry(skin).or_default().push(quote),
+                None => quotes.push(quote),
+            }
+        }
+
+        let mut seen = HashSet::new();
+        quotes.retain(|quote| seen.insert(quote.text.clone()));
+
+        let skins = match locale {
+            Locale::English => champion
+                .skins
+                .iter()
+                .map(|skin| {
+                    let mut quotes = skin_quotes.remove(&skin.name).unwrap_or_default();
+
+                    let mut seen = HashSet::new();
+                    quotes.retain(|quote| seen.insert(quote.text.clone()));
+
+                    (
+                        skin.name.clone(),
+                        SkinQuoteExport {
+                            quotes,
+                            icon: skin.icon.clone(),
+                            splash: skin.splash.clone(),
+                        },
+                    )
+                })
+                .collect(),
+            _ => HashMap::new(),
+        };
+
+        let champion_quotes = QuoteExport {
+            name: match locale {
+                Locale::English => None,
+                _ => Some(name),
+            },
+            title: champion.title.clone(),
+            blurb: champion.blurb.clone(),
+            partype: champion.partype.clone(),
+            info: champion.info.clone(),
+            stats: champion.stats.clone(),
+            quotes,
+            icon: champion.icon.clone(),
+            tags: champion.tags.clone(),
+            skins,
+        };
+
+        champion_quote_data.insert(champion.name.clone(), champion_quotes);
+    }
+
+    Ok(champion_quote_data)
+}
+
 #[tokio::main]
 async fn main() {
     tracing_subscriber::fmt::init();
@@ -262,51 +573,34 @@ async fn main() {
         }
     };
 
-    let champion_count = champions.len();
-    let mut champion_quote_data = HashMap::with_capacity(champion_count);
-
-    for (idx, champion) in champions.into_iter().enumerate() {
-        info!(
-            "Getting quotes for {} ({}/{})",
-            champion.name,
-            idx + 1,
-            champion_count
-        );
+    for locale in Locale::ALL {
+        let champion_quote_data =
+            match get_locale_quotes(&client, locale, locale.wiki_url(), &champions).await {
+                Ok(data) => data,
+                Err(e) => {
+                    error!("Failed to get {} quotes: {:?}", locale.code(), e);
+                    return;
+                }
+            };
 
-        let name_to_use = if champion.name.contains("&") {
-            &champion.id
-        } else {
-            &champion.name
+        let file_name = match locale {
+            Locale::English => String::from("quotes.json"),
+            _ => format!("quotes.{}.json", locale.code()),
         };
 
-        let quotes = match get_quotes(&client, &name_to_use).await {
-            Ok(quotes) => quotes,
+        info!("Writing results to {}", file_name);
+
+        let json = match simd_json::to_vec_pretty(&champion_quote_data) {
+            Ok(json) => json,
             Err(e) => {
-                error!("Failed to get quotes for {}: {:?}", champion.name, e);
+                error!("Failed to serialize quote export: {:?}", e);
                 return;
             }
         };
 
-        let champion_quotes = QuoteExport {
-            quotes,
-            icon: champion.icon,
+        match fs::write(&file_name, json).await {
+            Ok(_) => info!("Data written to {}", file_name),
+            Err(e) => error!("Failed to write to {}: {}", file_name, e),
         };
-
-        champion_quote_data.insert(champion.name, champion_quotes);
     }
-
-    info!("Writing results to quotes.json");
-
-    let json = match simd_json::to_vec_pretty(&champion_quote_data) {
-        Ok(json) => json,
-        Err(e) => {
-            error!("Failed to serialize quote export: {:?}", e);
-            return;
-        }
-    };
-
-    match fs::write("quotes.json", json).await {
-        Ok(_) => info!("Data written to quotes.json"),
-        Err(e) => error!("Failed to write to quotes.json: {}", e),
-    };
 }
@@ -1,14 +1,290 @@
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, HashSet},
+    iter,
+};
 
 use lazy_static::lazy_static;
+use rand::{prelude::IteratorRandom, thread_rng};
 
-use crate::ddragon::QuoteExport;
+use crate::{
+    ddragon::{Locale, Quote, QuoteExport},
+    matching::{self, QuoteIndex},
+};
 
-const QUOTES_JSON: &[u8] = include_bytes!("../quotes.json");
+/// The quotes exported for a locale by `update_quotes`.
+const fn dataset(locale: Locale) -> &'static [u8] {
+    match locale {
+        Locale::English => include_bytes!("../quotes.json"),
+        Locale::German => include_bytes!("../quotes.de_DE.json"),
+    }
+}
+
+// Nicknames players commonly use, keyed by their normalized form.
+const ALIASES: &[(&str, &str)] = &[
+    ("asol", "Aurelion Sol"),
+    ("blitz", "Blitzcrank"),
+    ("cait", "Caitlyn"),
+    ("cass", "Cassiopeia"),
+    ("cho", "Cho'Gath"),
+    ("ez", "Ezreal"),
+    ("fiddle", "Fiddlesticks"),
+    ("gp", "Gangplank"),
+    ("heimer", "Heimerdinger"),
+    ("j4", "Jarvan IV"),
+    ("jarvan", "Jarvan IV"),
+    ("kass", "Kassadin"),
+    ("kat", "Katarina"),
+    ("kha", "Kha'Zix"),
+    ("kog", "Kog'Maw"),
+    ("lb", "LeBlanc"),
+    ("lee", "Lee Sin"),
+    ("mf", "Miss Fortune"),
+    ("monkeyking", "Wukong"),
+    ("morde", "Mordekaiser"),
+    ("mundo", "Dr. Mundo"),
+    ("naut", "Nautilus"),
+    ("nunu", "Nunu & Willump"),
+    ("ori", "Orianna"),
+    ("rek", "Rek'Sai"),
+    ("tf", "Twisted Fate"),
+    ("tk", "Tahm Kench"),
+    ("trynd", "Tryndamere"),
+    ("vel", "Vel'Koz"),
+    ("willump", "Nunu & Willump"),
+    ("ww", "Warwick"),
+    ("xin", "Xin Zhao"),
+    ("yi", "Master Yi"),
+];
 
 lazy_static! {
     pub static ref CHAMPIONS: HashMap<String, QuoteExport> =
-        serde_json::from_slice(QUOTES_JSON).expect("Invalid JSON in quotes.json");
-    pub static ref ALL_CHAMPIONS_STRING: String =
-        CHAMPIONS.keys().fold(String::new(), |a, b| a + b + "\n");
+        load(dataset(Locale::English)).expect("Invalid JSON in quotes.json");
+    // Champions missing from a localized dataset are quoted in English.
+    pub static ref LOCALIZED: HashMap<Locale, HashMap<String, QuoteExport>> = Locale::ALL
+        .into_iter()
+        .filter(|locale| *locale != Locale::English)
+        .map(|locale| {
+            let champions = load(dataset(locale)).unwrap_or_else(|e| {
+                panic!("Invalid JSON in quotes.{}.json: {}", locale.code(), e)
+            });
+
+            (locale, champions)
+        })
+        .collect();
+    pub static ref QUOTE_INDEX: QuoteIndex = QuoteIndex::build(
+        iter::once((Locale::English, &*CHAMPIONS))
+            .chain(LOCALIZED.iter().map(|(locale, champions)| (*locale, champions)))
+    );
+}
+
+fn load(data: &[u8]) -> Result<HashMap<String, QuoteExport>, serde_json::Error> {
+    let mut champions: HashMap<String, QuoteExport> = serde_json::from_slice(data)?;
+
+    for champion in champions.values_mut() {
+        dedup(&mut champion.quotes);
+
+        for skin in champion.skins.values_mut() {
+            dedup(&mut skin.quotes);
+        }
+    }
+
+    Ok(champions)
+}
+
+fn dedup(quotes: &mut Vec<Quote>) {
+    let mut seen = HashSet::new();
+    quotes.retain(|quote| seen.insert(quote.text.clone()));
+}
+
+/// The set of lines and the avatar someone is quoted with.
+pub struct Voice {
+    pub key: String,
+    pub champion: &'static str,
+    pub locale: Locale,
+    pub quotes: &'static [Quote],
+    pub icon: &'static str,
+}
+
+pub fn voice_key(locale: Locale, champion: &str, skin: Option<&str>) -> String {
+    let key = match skin {
+        Some(skin) => format!("{}/{}", champion, skin),
+        None => champion.to_string(),
+    };
+
+    match locale {
+        Locale::English => key,
+        _ => format!("{}:{}", locale.code(), key),
+    }
+}
+
+/// Resolves the voice for a champion and optional skin. Skins without their
+/// own voice pack keep their avatar but fall back to the champion's lines.
+pub fn voice(champion: &str, skin: Option<&str>, locale: Locale) -> Option<Voice> {
+    let (champion, data) = CHAMPIONS.get_key_value(champion)?;
+
+    let (locale, lines) = match LOCALIZED.get(&locale).and_then(|c| c.get(champion)) {
+        Some(localized) if !localized.quotes.is_empty() => (locale, localized),
+        _ => (Locale::English, data),
+    };
+
+    let skin = skin.and_then(|skin| data.skins.get_key_value(skin));
+
+    let skin_lines = skin
+        .and_then(|(skin, _)| lines.skins.get_key_value(skin))
+        .filter(|(_, skin_data)| !skin_data.quotes.is_empty());
+
+    let (key, quotes) = match skin_lines {
+        Some((skin, skin_data)) => (voice_key(locale, champion, Some(skin)), &skin_data.quotes),
+        None => (voice_key(locale, champion, None), &lines.quotes),
+    };
+
+    let icon = match skin {
+        Some((_, skin_data)) => &skin_data.icon,
+        None => &data.icon,
+    };
+
+    Some(Voice {
+        key,
+        champion,
+        locale,
+        quotes,
+        icon,
+    })
+}
+
+/// Resolves what someone typed to a champion, ignoring case, spaces and
+/// punctuation and accepting common nicknames.
+pub fn resolve(input: &str) -> Option<&'static str> {
+    let input = matching::normalize(input);
+
+    if let Some((_, name)) = ALIASES.iter().find(|(alias, _)| *alias == input) {
+        return CHAMPIONS
+            .get_key_value(*name)
+            .map(|(name, _)| name.as_str());
+    }
+
+    CHAMPIONS
+        .keys()
+        .find(|name| matching::normalize(name) == input)
+        .map(String::as_str)
+}
+
+/// Champions that `input` could be referring to, best match first. Exact
+/// matches and nicknames rank above prefixes, then substrings, then names
+/// within a few typos.
+pub fn suggest(input: &str) -> Vec<&'static str> {
+    let input = matching::normalize(input);
+    let max_distance = (input.len() / 3).max(1);
+
+    let alias = ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == input)
+        .map(|(_, name)| *name);
+
+    let mut ranked: Vec<((u8, usize), &'static str)> = CHAMPIONS
+        .keys()
+        .filter_map(|name| {
+            let normalized = matching::normalize(name);
+
+            let rank = if normalized == input || alias == Some(name.as_str()) {
+                (0, 0)
+            } else if normalized.starts_with(&input) {
+                (1, normalized.len() - input.len())
+            } else if normalized.contains(&input) {
+                (2, normalized.len() - input.len())
+            } else {
+                // Compare against the start of the name too, so typos in a
+                // partially typed name still match.
+                let prefix: String = normalized.chars().take(input.chars().count()).collect();
+                let distance = matching::edit_distance(&input, &normalized)
+                    .min(matching::edit_distance(&input, &prefix));
+
+                if distance > max_distance {
+                    return None;
+                }
+
+                (3, distance)
+            };
+
+            Some((rank, name.as_str()))
+        })
+        .collect();
+
+    ranked.sort();
+
+    ranked.into_iter().map(|(_, name)| name).collect()
+}
+
+/// Every quote matching all words of `query`, labelled with the champion or
+/// skin that says it.
+pub fn search(query: &str) -> Vec<(&'static str, &'static Quote)> {
+    let query_terms = matching::terms(query);
+    let needle = query.to_lowercase();
+
+    let mut names: Vec<&'static String> = CHAMPIONS.keys().collect();
+    names.sort();
+
+    let mut results = Vec::new();
+
+    for name in names {
+        let data = &CHAMPIONS[name];
+
+        let mut skins: Vec<(&'static String, &'static Vec<Quote>)> = data
+            .skins
+            .iter()
+            .map(|(skin, skin_data)| (skin, &skin_data.quotes))
+            .collect();
+        skins.sort_by_key(|(skin, _)| *skin);
+
+        let voices = iter::once((name, None, &data.quotes)).chain(
+            skins
+                .into_iter()
+                .map(|(skin, quotes)| (skin, Some(skin), quotes)),
+        );
+
+        for (label, skin, quotes) in voices {
+            // Queries made up of only stop words are matched literally.
+            if query_terms.is_empty() {
+                results.extend(
+                    quotes
+                        .iter()
+                        .filter(|quote| quote.text.to_lowercase().contains(&needle))
+                        .map(|quote| (label.as_str(), quote)),
+                );
+            } else {
+                let key = voice_key(Locale::English, name, skin.map(String::as_str));
+
+                results.extend(
+                    QUOTE_INDEX
+                        .containing(&key, &query_terms)
+                        .into_iter()
+                        .filter_map(|idx| quotes.get(idx))
+                        .map(|quote| (label.as_str(), quote)),
+                );
+            }
+        }
+    }
+
+    results
+}
+
+/// The champion's name as it is spelled in `locale`.
+pub fn display_name(champion: &str, locale: Locale) -> &str {
+    LOCALIZED
+        .get(&locale)
+        .and_then(|champions| champions.get(champion))
+        .and_then(|data| data.name.as_deref())
+        .unwrap_or(champion)
+}
+
+/// Picks a random champion, restricted to those with any of `tags` unless
+/// `tags` is empty.
+pub fn random_champion(tags: &[String]) -> Option<&'static str> {
+    let mut rng = thread_rng();
+
+    CHAMPIONS
+        .iter()
+        .filter(|(_, data)| tags.is_empty() || data.tags.iter().any(|tag| tags.contains(tag)))
+        .map(|(name, _)| name.as_str())
+        .choose(&mut rng)
 }
@@ -0,0 +1,70 @@
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_model::{
+    application::callback::InteractionResponse, channel::message::MessageFlags, guild::Permissions,
+};
+use twilight_util::builder::CallbackDataBuilder;
+
+use crate::Error;
+
+use super::Context;
+
+#[derive(CommandModel, CreateCommand, Debug, PartialEq, Eq)]
+#[command(
+    name = "audio",
+    desc = "Set whether quotes come with the champion's voice line attached"
+)]
+pub struct AudioCommand {
+    #[command(desc = "Whether to attach voice lines")]
+    pub enabled: bool,
+    #[command(desc = "Change the default for the whole server instead of just for you")]
+    pub server: Option<bool>,
+}
+
+impl AudioCommand {
+    pub async fn run(&self, context: Context) -> Result<(), Error> {
+        let state = if self.enabled { "on" } else { "off" };
+
+        let text = if self.server.unwrap_or(false) {
+            match context.guild_id {
+                Some(guild_id) if context.permissions.contains(Permissions::MANAGE_GUILD) => {
+                    match context
+                        .database
+                        .set_guild_audio(guild_id, self.enabled)
+                        .await
+                    {
+                        Ok(_) => format!("Done. Voice lines are now {} in this server.", state),
+                        Err(_) => String::from("Failed to update the server's audio mode."),
+                    }
+                }
+                Some(_) => String::from("You need the Manage Server permission to do this."),
+                None => String::from("This command can only be used in servers."),
+            }
+        } else {
+            match context
+                .database
+                .set_audio(context.user_id, Some(self.enabled))
+                .await
+            {
+                Ok(_) => format!("Done. Voice lines are now {} for you.", state),
+                Err(_) => String::from("Failed to update your audio mode."),
+            }
+        };
+
+        let reply = CallbackDataBuilder::new()
+            .content(text)
+            .flags(MessageFlags::EPHEMERAL)
+            .build();
+
+        context
+            .http
+            .interaction_callback(
+                context.interaction_id,
+                &context.interaction_token,
+                &InteractionResponse::ChannelMessageWithSource(reply),
+            )
+            .exec()
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,204 @@
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_model::{
+    application::callback::{Autocomplete, InteractionResponse},
+    channel::{
+        embed::{Embed, EmbedField, EmbedFooter, EmbedThumbnail},
+        message::MessageFlags,
+    },
+};
+use twilight_util::builder::CallbackDataBuilder;
+
+use crate::{
+    champions::{self, CHAMPIONS},
+    ddragon::ChampionStats,
+    Error,
+};
+
+use super::{champion_choices, Context};
+
+#[derive(CommandModel, CreateCommand, Debug, PartialEq, Eq)]
+#[command(
+    name = "champion",
+    desc = "View a League of Legends champion's lore and stats"
+)]
+pub struct ChampionCommand {
+    #[command(autocomplete = true, desc = "The champion to look up")]
+    pub name: String,
+    #[command(
+        min_value = 1,
+        max_value = 18,
+        desc = "Show stats at this level instead of base stats and growth"
+    )]
+    pub level: Option<i64>,
+}
+
+impl ChampionCommand {
+    pub async fn run(&self, context: Context) -> Result<(), Error> {
+        let locale = context
+            .database
+            .get_locale(context.user_id, context.guild_id)
+            .await
+            .unwrap_or_default();
+
+        let reply = match champions::resolve(&self.name) {
+            // Data exported without stats would only show an icon.
+            Some(champion) if CHAMPIONS[champion].stats.is_none() => CallbackDataBuilder::new()
+                .content(format!(
+                    "No stats are available for {} yet.",
+                    champions::display_name(champion, locale)
+                ))
+                .flags(MessageFlags::EPHEMERAL)
+                .build(),
+            Some(champion) => CallbackDataBuilder::new()
+                .embeds([info_embed(
+                    champion,
+                    champions::display_name(champion, locale),
+                    self.level.map(|level| level as u8),
+                )])
+                .build(),
+            None => CallbackDataBuilder::new()
+                .content(String::from("Invalid champion."))
+                .flags(MessageFlags::EPHEMERAL)
+                .build(),
+        };
+
+        context
+            .http
+            .interaction_callback(
+                context.interaction_id,
+                &context.interaction_token,
+                &InteractionResponse::ChannelMessageWithSource(reply),
+            )
+            .exec()
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn autocomplete(&self, context: Context) -> Result<(), Error> {
+        let matches = champion_choices(&self.name);
+
+        context
+            .http
+            .interaction_callback(
+                context.interaction_id,
+                &context.interaction_token,
+                &InteractionResponse::Autocomplete(Autocomplete { choices: matches }),
+            )
+            .exec()
+            .await?;
+
+        Ok(())
+    }
+}
+
+fn info_embed(champion: &str, name: &str, level: Option<u8>) -> Embed {
+    let data = &CHAMPIONS[champion];
+
+    let field = |name: &str, value: String| EmbedField {
+        inline: true,
+        name: name.to_string(),
+        value,
+    };
+
+    let mut fields = Vec::new();
+
+    if let Some(info) = &data.info {
+        fields.push(field("Difficulty", format!("{}/10", info.difficulty)));
+        fields.push(field(
+            "Ratings",
+            format!(
+                "Attack {}, Defense {}, Magic {}",
+                info.attack, info.defense, info.magic
+            ),
+        ));
+    }
+
+    if !data.tags.is_empty() {
+        fields.push(field("Classes", data.tags.join(", ")));
+    }
+
+    if let Some(stats) = &data.stats {
+        // Either the value at the requested level, or the base value and how
+        // much it grows per level.
+        let stat = |base: f32, per_level: f32, precision: usize| match level {
+            Some(level) => format!(
+                "{:.*}",
+                precision,
+                ChampionStats::at_level(base, per_level, level)
+            ),
+            None if per_level == 0.0 => format!("{:.*}", precision, base),
+            None => format!("{:.*} (+{:.*})", precision, base, precision, per_level),
+        };
+
+        let resource = if data.partype.is_empty() {
+            "Mana"
+        } else {
+            &data.partype
+        };
+
+        fields.push(field("Health", stat(stats.hp, stats.hpperlevel, 0)));
+        fields.push(field(
+            "Health regen",
+            stat(stats.hpregen, stats.hpregenperlevel, 1),
+        ));
+        fields.push(field(resource, stat(stats.mp, stats.mpperlevel, 0)));
+        fields.push(field(
+            &format!("{} regen", resource),
+            stat(stats.mpregen, stats.mpregenperlevel, 1),
+        ));
+        fields.push(field(
+            "Attack damage",
+            stat(stats.attackdamage, stats.attackdamageperlevel, 0),
+        ));
+        fields.push(field(
+            "Attack speed",
+            match level {
+                Some(level) => format!("{:.3}", stats.attack_speed_at_level(level)),
+                None => format!("{:.3} (+{}%)", stats.attackspeed, stats.attackspeedperlevel),
+            },
+        ));
+        fields.push(field("Armor", stat(stats.armor, stats.armorperlevel, 0)));
+        fields.push(field(
+            "Magic resist",
+            stat(stats.spellblock, stats.spellblockperlevel, 0),
+        ));
+        fields.push(field("Attack range", stats.attackrange.to_string()));
+        fields.push(field("Move speed", format!("{:.0}", stats.movespeed)));
+    }
+
+    let description = match (data.title.as_str(), data.blurb.as_str()) {
+        ("", "") => None,
+        (title, "") => Some(format!("*{}*", title)),
+        ("", blurb) => Some(blurb.to_string()),
+        (title, blurb) => Some(format!("*{}*\n\n{}", title, blurb)),
+    };
+
+    Embed {
+        author: None,
+        color: None,
+        description,
+        fields,
+        footer: Some(EmbedFooter {
+            icon_url: None,
+            proxy_icon_url: None,
+            text: match level {
+                Some(level) => format!("Stats at level {}", level),
+                None => String::from("Base stats, growth per level in parentheses"),
+            },
+        }),
+        image: None,
+        kind: String::from("rich"),
+        provider: None,
+        thumbnail: Some(EmbedThumbnail {
+            height: None,
+            proxy_url: None,
+            url: data.icon.clone(),
+            width: None,
+        }),
+        timestamp: None,
+        title: Some(name.to_string()),
+        url: None,
+        video: None,
+    }
+}
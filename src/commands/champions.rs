@@ -1,31 +1,76 @@
 use twilight_interactions::command::{CommandModel, CreateCommand};
-use twilight_model::{application::callback::InteractionResponse, channel::message::MessageFlags};
+use twilight_model::{
+    application::{
+        callback::{CallbackData, InteractionResponse},
+        component::{
+            button::ButtonStyle, select_menu::SelectMenuOption, ActionRow, Button, Component,
+            SelectMenu,
+        },
+    },
+    channel::{
+        embed::{Embed, EmbedThumbnail},
+        message::MessageFlags,
+    },
+};
 use twilight_util::builder::CallbackDataBuilder;
 
-use crate::{champions::ALL_CHAMPIONS_STRING, Error};
+use std::iter;
+
+use crate::{champions::CHAMPIONS, ddragon::ChampionTag, Error};
 
 use super::Context;
 
+// Messages can't have more than 10 embeds.
+const PAGE_SIZE: usize = 10;
+
 #[derive(CommandModel, CreateCommand, Debug, PartialEq, Eq)]
 #[command(
     name = "champions",
     desc = "View all available League of Legends champions"
 )]
-pub struct ChampionsCommand {}
+pub struct ChampionsCommand {
+    #[command(desc = "Only show champions with this class")]
+    pub tag: Option<ChampionTag>,
+}
 
 impl ChampionsCommand {
     pub async fn run(&self, context: Context) -> Result<(), Error> {
-        let reply = CallbackDataBuilder::new()
-            .content(ALL_CHAMPIONS_STRING.clone())
-            .flags(MessageFlags::EPHEMERAL)
-            .build();
+        context
+            .http
+            .interaction_callback(
+                context.interaction_id,
+                &context.interaction_token,
+                &InteractionResponse::ChannelMessageWithSource(listing(self.tag, 0)),
+            )
+            .exec()
+            .await?;
+
+        Ok(())
+    }
+
+    /// Handles the class filter, whose custom ID is `champions:filter`, and
+    /// the Previous and Next buttons, whose custom ID is
+    /// `champions:<page>:<tag>`.
+    pub async fn paginate(
+        context: Context,
+        arguments: &str,
+        values: &[String],
+    ) -> Result<(), Error> {
+        let (tag, page) = if arguments == "filter" {
+            (values.first().map(String::as_str).unwrap_or_default(), 0)
+        } else {
+            match arguments.split_once(':') {
+                Some((page, tag)) => (tag, page.parse().unwrap_or(0)),
+                None => return Ok(()),
+            }
+        };
 
         context
             .http
             .interaction_callback(
                 context.interaction_id,
                 &context.interaction_token,
-                &InteractionResponse::ChannelMessageWithSource(reply),
+                &InteractionResponse::UpdateMessage(listing(ChampionTag::from_name(tag), page)),
             )
             .exec()
             .await?;
@@ -33,3 +78,118 @@ impl ChampionsCommand {
         Ok(())
     }
 }
+
+fn listing(tag: Option<ChampionTag>, page: usize) -> CallbackData {
+    let mut champions: Vec<_> = CHAMPIONS
+        .iter()
+        .filter(|(_, data)| match tag {
+            Some(tag) => data.tags.iter().any(|name| name == tag.as_str()),
+            None => true,
+        })
+        .collect();
+    champions.sort_by_key(|(name, _)| *name);
+
+    let pages = champions.len().div_ceil(PAGE_SIZE);
+    let page = page.min(pages.saturating_sub(1));
+
+    let embeds = champions
+        .iter()
+        .skip(page * PAGE_SIZE)
+        .take(PAGE_SIZE)
+        .map(|(name, data)| Embed {
+            author: None,
+            color: None,
+            description: Some(format!("{} quotes", data.quotes.len())),
+            fields: Vec::new(),
+            footer: None,
+            image: None,
+            kind: String::from("rich"),
+            provider: None,
+            thumbnail: Some(EmbedThumbnail {
+                height: None,
+                proxy_url: None,
+                url: data.icon.clone(),
+                width: None,
+            }),
+            timestamp: None,
+            title: Some(name.to_string()),
+            url: None,
+            video: None,
+        });
+
+    let tag_value = tag.map(ChampionTag::as_str).unwrap_or_default();
+
+    let filter = Component::ActionRow(ActionRow {
+        components: vec![Component::SelectMenu(SelectMenu {
+            custom_id: String::from("champions:filter"),
+            disabled: false,
+            max_values: Some(1),
+            min_values: Some(1),
+            options: iter::once(SelectMenuOption {
+                default: tag.is_none(),
+                description: None,
+                emoji: None,
+                label: String::from("All classes"),
+                value: String::from("all"),
+            })
+            // Classes no champion has would only ever show an empty list.
+            .chain(
+                ChampionTag::ALL
+                    .into_iter()
+                    .filter(|option| {
+                        CHAMPIONS
+                            .values()
+                            .any(|data| data.tags.iter().any(|name| name == option.as_str()))
+                    })
+                    .map(|option| SelectMenuOption {
+                        default: tag == Some(option),
+                        description: None,
+                        emoji: None,
+                        label: option.as_str().to_string(),
+                        value: option.as_str().to_string(),
+                    }),
+            )
+            .collect(),
+            placeholder: None,
+        })],
+    });
+
+    let buttons = Component::ActionRow(ActionRow {
+        components: vec![
+            Component::Button(Button {
+                custom_id: Some(format!(
+                    "champions:{}:{}",
+                    page.saturating_sub(1),
+                    tag_value
+                )),
+                disabled: page == 0,
+                emoji: None,
+                label: Some(String::from("Previous")),
+                style: ButtonStyle::Secondary,
+                url: None,
+            }),
+            Component::Button(Button {
+                custom_id: Some(format!("champions:{}:{}", page + 1, tag_value)),
+                disabled: page + 1 >= pages,
+                emoji: None,
+                label: Some(String::from("Next")),
+                style: ButtonStyle::Secondary,
+                url: None,
+            }),
+        ],
+    });
+
+    let content = match tag {
+        Some(tag) if champions.is_empty() => {
+            format!("No champions match the {} class.", tag.as_str())
+        }
+        _ => format!("Page {} of {}", page + 1, pages.max(1)),
+    };
+
+    CallbackDataBuilder::new()
+        .content(content)
+        .embeds(embeds)
+        .components([filter, buttons])
+        .flags(MessageFlags::EPHEMERAL)
+        .build()
+}
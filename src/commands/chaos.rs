@@ -0,0 +1,66 @@
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_model::{application::callback::InteractionResponse, channel::message::MessageFlags};
+use twilight_util::builder::CallbackDataBuilder;
+
+use crate::{champions, ddragon::ChampionTag, Error};
+
+use super::Context;
+
+#[derive(CommandModel, CreateCommand, Debug, PartialEq, Eq)]
+#[command(
+    name = "chaos",
+    desc = "Be quoted as a different random champion every time"
+)]
+pub struct ChaosCommand {
+    #[command(desc = "Whether to enable chaos mode")]
+    pub enabled: bool,
+    #[command(desc = "Only pick champions with this class")]
+    pub tag: Option<ChampionTag>,
+    #[command(desc = "Also pick champions with this class")]
+    pub other_tag: Option<ChampionTag>,
+}
+
+impl ChaosCommand {
+    pub async fn run(&self, context: Context) -> Result<(), Error> {
+        let tags: Vec<&str> = self
+            .tag
+            .iter()
+            .chain(self.other_tag.iter())
+            .map(|tag| tag.as_str())
+            .collect();
+
+        let tag_names: Vec<String> = tags.iter().map(ToString::to_string).collect();
+
+        let text = if self.enabled && champions::random_champion(&tag_names).is_none() {
+            format!("No champions match {}.", tags.join(" or "))
+        } else {
+            match context
+                .database
+                .set_chaos(context.user_id, self.enabled, &tags)
+                .await
+            {
+                Ok(_) if !self.enabled => String::from("Done. Chaos mode is now off."),
+                Ok(_) if tags.is_empty() => String::from("Done. Chaos mode is now on."),
+                Ok(_) => format!("Done. Chaos mode is now on for {}.", tags.join(" or ")),
+                Err(_) => String::from("Failed to update chaos mode."),
+            }
+        };
+
+        let reply = CallbackDataBuilder::new()
+            .content(text)
+            .flags(MessageFlags::EPHEMERAL)
+            .build();
+
+        context
+            .http
+            .interaction_callback(
+                context.interaction_id,
+                &context.interaction_token,
+                &InteractionResponse::ChannelMessageWithSource(reply),
+            )
+            .exec()
+            .await?;
+
+        Ok(())
+    }
+}
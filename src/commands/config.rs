@@ -0,0 +1,196 @@
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_model::{
+    application::{
+        callback::InteractionResponse, interaction::application_command::InteractionChannel,
+    },
+    channel::message::MessageFlags,
+    guild::Permissions,
+};
+use twilight_util::builder::CallbackDataBuilder;
+
+use crate::Error;
+
+use super::Context;
+
+#[derive(CommandModel, CreateCommand, Debug, PartialEq, Eq)]
+#[command(name = "config", desc = "Configure quoting in this server")]
+pub enum ConfigCommand {
+    #[command(name = "show")]
+    Show(ConfigShowCommand),
+    #[command(name = "ignore")]
+    Ignore(ConfigIgnoreCommand),
+    #[command(name = "unignore")]
+    Unignore(ConfigUnignoreCommand),
+    #[command(name = "rate")]
+    Rate(ConfigRateCommand),
+    #[command(name = "quoting")]
+    Quoting(ConfigQuotingCommand),
+}
+
+#[derive(CommandModel, CreateCommand, Debug, PartialEq, Eq)]
+#[command(name = "show", desc = "View this server's settings")]
+pub struct ConfigShowCommand {}
+
+#[derive(CommandModel, CreateCommand, Debug, PartialEq, Eq)]
+#[command(name = "ignore", desc = "Stop quoting people in a channel or category")]
+pub struct ConfigIgnoreCommand {
+    #[command(
+        desc = "The channel or category to ignore",
+        channel_types = "guild_text guild_news guild_category"
+    )]
+    pub channel: InteractionChannel,
+}
+
+#[derive(CommandModel, CreateCommand, Debug, PartialEq, Eq)]
+#[command(
+    name = "unignore",
+    desc = "Quote people in an ignored channel or category again"
+)]
+pub struct ConfigUnignoreCommand {
+    #[command(
+        desc = "The channel or category to stop ignoring",
+        channel_types = "guild_text guild_news guild_category"
+    )]
+    pub channel: InteractionChannel,
+}
+
+#[derive(CommandModel, CreateCommand, Debug, PartialEq, Eq)]
+#[command(
+    name = "rate",
+    desc = "Set the quote rate for people who didn't pick their own"
+)]
+pub struct ConfigRateCommand {
+    #[command(
+        desc = "Percentage of messages to quote people on, leave out to reset",
+        min_value = 0,
+        max_value = 100
+    )]
+    pub percentage: Option<i64>,
+}
+
+#[derive(CommandModel, CreateCommand, Debug, PartialEq, Eq)]
+#[command(name = "quoting", desc = "Turn quoting in this server on or off")]
+pub struct ConfigQuotingCommand {
+    #[command(desc = "Whether to quote people in this server")]
+    pub enabled: bool,
+}
+
+impl ConfigCommand {
+    pub async fn run(&self, context: Context) -> Result<(), Error> {
+        let text = match context.guild_id {
+            Some(guild_id) if context.permissions.contains(Permissions::MANAGE_GUILD) => {
+                self.execute(&context, guild_id).await
+            }
+            Some(_) => String::from("You need the Manage Server permission to do this."),
+            None => String::from("This command can only be used in servers."),
+        };
+
+        let reply = CallbackDataBuilder::new()
+            .content(text)
+            .flags(MessageFlags::EPHEMERAL)
+            .build();
+
+        context
+            .http
+            .interaction_callback(
+                context.interaction_id,
+                &context.interaction_token,
+                &InteractionResponse::ChannelMessageWithSource(reply),
+            )
+            .exec()
+            .await?;
+
+        Ok(())
+    }
+
+    async fn execute(&self, context: &Context, guild_id: i64) -> String {
+        match self {
+            Self::Show(_) => show(context, guild_id).await,
+            Self::Ignore(command) => set_ignored(context, guild_id, &command.channel, true).await,
+            Self::Unignore(command) => {
+                set_ignored(context, guild_id, &command.channel, false).await
+            }
+            Self::Rate(command) => command.run(context, guild_id).await,
+            Self::Quoting(command) => command.run(context, guild_id).await,
+        }
+    }
+}
+
+async fn show(context: &Context, guild_id: i64) -> String {
+    let settings = match context.database.get_guild_settings(guild_id).await {
+        Ok(settings) => settings,
+        Err(_) => return String::from("Failed to fetch the server's settings."),
+    };
+
+    let mut ignored: Vec<String> = settings
+        .ignored
+        .iter()
+        .map(|channel_id| format!("<#{}>", channel_id))
+        .collect();
+    ignored.sort();
+
+    format!(
+        "Quoting: {}\nDefault quote rate: {}\nIgnored: {}",
+        if settings.enabled { "on" } else { "off" },
+        settings
+            .default_rate
+            .map_or_else(|| String::from("not set"), |rate| format!("{}%", rate)),
+        if ignored.is_empty() {
+            String::from("nothing")
+        } else {
+            ignored.join(", ")
+        }
+    )
+}
+
+async fn set_ignored(
+    context: &Context,
+    guild_id: i64,
+    channel: &InteractionChannel,
+    ignored: bool,
+) -> String {
+    match context
+        .database
+        .set_channel_ignored(guild_id, channel.id.get() as i64, ignored)
+        .await
+    {
+        Ok(true) if ignored => format!("Done. Nobody will be quoted in <#{}>.", channel.id),
+        Ok(true) => format!("Done. People will be quoted in <#{}> again.", channel.id),
+        Ok(false) if ignored => format!("<#{}> is already ignored.", channel.id),
+        Ok(false) => format!("<#{}> isn't ignored.", channel.id),
+        Err(_) => String::from("Failed to update the ignored channels."),
+    }
+}
+
+impl ConfigRateCommand {
+    async fn run(&self, context: &Context, guild_id: i64) -> String {
+        match context
+            .database
+            .set_guild_default_rate(guild_id, self.percentage)
+            .await
+        {
+            Ok(_) => match self.percentage {
+                Some(percentage) => format!(
+                    "Done. People who didn't pick a rate are now quoted on {}% of messages.",
+                    percentage
+                ),
+                None => String::from("Done. People who didn't pick a rate use the default again."),
+            },
+            Err(_) => String::from("Failed to update the server's quote rate."),
+        }
+    }
+}
+
+impl ConfigQuotingCommand {
+    async fn run(&self, context: &Context, guild_id: i64) -> String {
+        match context
+            .database
+            .set_guild_enabled(guild_id, self.enabled)
+            .await
+        {
+            Ok(_) if self.enabled => String::from("Done. People will be quoted in this server."),
+            Ok(_) => String::from("Done. Nobody will be quoted in this server."),
+            Err(_) => String::from("Failed to update the server's settings."),
+        }
+    }
+}
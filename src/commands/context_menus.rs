@@ -0,0 +1,205 @@
+use twilight_model::{
+    application::{
+        callback::InteractionResponse,
+        command::{Command, CommandType},
+        interaction::application_command::CommandData,
+    },
+    channel::{message::MessageFlags, Message},
+    user::User,
+};
+use twilight_util::builder::{command::CommandBuilder, CallbackDataBuilder};
+
+use crate::{db::QuoteTrigger, selection, Error};
+
+use super::{quoting_blocked, Context, WhoisCommand};
+
+pub const WHICH_CHAMPION: &str = "Which champion is this?";
+pub const ANSWER: &str = "Answer as my champion";
+
+/// The right-click commands, registered next to the slash commands.
+pub fn commands() -> [Command; 2] {
+    [
+        CommandBuilder::new(WHICH_CHAMPION.to_string(), String::new(), CommandType::User).build(),
+        CommandBuilder::new(ANSWER.to_string(), String::new(), CommandType::Message).build(),
+    ]
+}
+
+/// Shows the champion of the user that was right-clicked.
+pub struct WhichChampionCommand {
+    pub user: User,
+}
+
+impl WhichChampionCommand {
+    pub fn from_data(data: CommandData) -> Option<Self> {
+        let user = data.resolved?.users.into_values().next()?;
+
+        Some(Self { user })
+    }
+
+    pub async fn run(self, context: Context) -> Result<(), Error> {
+        WhoisCommand { user: self.user }.run(context).await
+    }
+}
+
+/// Quotes the invoker's champion in response to the message that was
+/// right-clicked.
+pub struct AnswerCommand {
+    pub message: Message,
+}
+
+impl AnswerCommand {
+    pub fn from_data(data: CommandData) -> Option<Self> {
+        let message = data.resolved?.messages.into_values().next()?;
+
+        Some(Self { message })
+    }
+
+    pub async fn run(self, context: Context) -> Result<(), Error> {
+        // Discord wants a response within three seconds, picking a line and
+        // posting it through the webhook can take longer.
+        let reply = CallbackDataBuilder::new()
+            .flags(MessageFlags::EPHEMERAL)
+            .build();
+
+        context
+            .http
+            .interaction_callback(
+                context.interaction_id,
+                &context.interaction_token,
+                &InteractionResponse::DeferredChannelMessageWithSource(reply),
+            )
+            .exec()
+            .await?;
+
+        let text = self.answer(&context).await;
+
+        if let Ok(request) = context
+            .http
+            .update_interaction_original(&context.interaction_token)?
+            .content(Some(&text))
+        {
+            request.exec().await?;
+        }
+
+        Ok(())
+    }
+
+    async fn answer(&self, context: &Context) -> String {
+        if let Some(reason) = quoting_blocked(context, self.message.channel_id).await {
+            return reason.to_string();
+        }
+
+        let settings = match context.database.get_user(context.user_id).await {
+            Ok(Some(settings)) => settings,
+            Ok(None) => {
+                return String::from("You don't have a champion yet, pick one or use /optin.")
+            }
+            Err(_) => return String::from("Failed to fetch your champion."),
+        };
+
+        let locale = context
+            .database
+            .get_locale(context.user_id, context.guild_id)
+            .await
+            .unwrap_or_default();
+
+        let voice = selection::user_voice(&settings, locale);
+
+        let voice = match voice {
+            Some(voice) => voice,
+            None => return String::from("Invalid champion."),
+        };
+
+        // Answer with a line aimed at the author's champion when there is one.
+        let target = if self.message.author.bot {
+            None
+        } else {
+            context
+                .database
+                .get_user(self.message.author.id.get() as i64)
+                .await
+                .ok()
+                .flatten()
+                .filter(|target| target.opted_in != Some(false))
+                .map(|target| target.champion)
+        };
+
+        let quote_idx = selection::pick_quote(
+            &context.database,
+            context.user_id,
+            context.guild_id,
+            &voice,
+            &self.message.content,
+            target.as_deref(),
+        )
+        .await
+        .ok()
+        .flatten();
+
+        let quote = match quote_idx.and_then(|idx| voice.quotes.get(idx)) {
+            Some(quote) => quote,
+            None => return String::from("Your champion has nothing to say."),
+        };
+
+        // Webhooks can't reply, so link the message being answered instead.
+        let mut snippet: String = self
+            .message
+            .content
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .chars()
+            .take(100)
+            .collect();
+
+        if snippet.trim().is_empty() {
+            snippet = String::from("Jump to message");
+        }
+
+        let link = format!(
+            "https://discord.com/channels/{}/{}/{}",
+            context
+                .guild_id
+                .map_or_else(|| String::from("@me"), |id| id.to_string()),
+            self.message.channel_id,
+            self.message.id
+        );
+
+        let content = format!(
+            "> [{}]({})\n{}",
+            snippet.replace(['[', ']'], ""),
+            link,
+            quote.text
+        );
+
+        match context
+            .webhooks
+            .execute(
+                self.message.channel_id,
+                &context.display_name,
+                voice.icon,
+                &content,
+                &[],
+            )
+            .await
+        {
+            Ok(true) => {
+                let _ = context
+                    .database
+                    .log_quote(
+                        context.user_id,
+                        context.guild_id,
+                        self.message.channel_id.get() as i64,
+                        &voice,
+                        &quote.text,
+                        QuoteTrigger::Command,
+                    )
+                    .await;
+
+                String::from("Sent.")
+            }
+            Ok(false) => String::from("Quotes can't be posted in this channel."),
+            Err(_) => String::from("Failed to send the quote."),
+        }
+    }
+}
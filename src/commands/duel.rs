@@ -0,0 +1,232 @@
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_model::{
+    application::callback::{CallbackData, InteractionResponse},
+    channel::{
+        embed::{Embed, EmbedField, EmbedFooter},
+        message::MessageFlags,
+    },
+    user::User,
+};
+use twilight_util::builder::CallbackDataBuilder;
+
+use crate::{champions, db::DuelRecord, duel, Error};
+
+use super::Context;
+
+// Embed descriptions are capped at 4096 characters.
+const MAX_DESCRIPTION: usize = 4000;
+
+#[derive(CommandModel, CreateCommand, Debug, PartialEq, Eq)]
+#[command(name = "duel", desc = "Pit your champion against someone else's")]
+pub struct DuelCommand {
+    #[command(desc = "The person to duel")]
+    pub user: User,
+    #[command(
+        min_value = 1,
+        max_value = 18,
+        desc = "The level both champions fight at, defaults to 1"
+    )]
+    pub level: Option<i64>,
+}
+
+impl DuelCommand {
+    pub async fn run(&self, context: Context) -> Result<(), Error> {
+        let reply = self.duel(&context).await;
+
+        context
+            .http
+            .interaction_callback(
+                context.interaction_id,
+                &context.interaction_token,
+                &InteractionResponse::ChannelMessageWithSource(reply),
+            )
+            .exec()
+            .await?;
+
+        Ok(())
+    }
+
+    async fn duel(&self, context: &Context) -> CallbackData {
+        let opponent_id = self.user.id.get() as i64;
+
+        if opponent_id == context.user_id {
+            return ephemeral("You can't duel yourself.");
+        }
+
+        if self.user.bot {
+            return ephemeral("Bots don't have champions.");
+        }
+
+        let own = match context.database.get_user(context.user_id).await {
+            Ok(Some(settings)) => settings,
+            Ok(None) => return ephemeral("You don't have a champion yet, pick one or use /optin."),
+            Err(_) => return ephemeral("Failed to fetch your champion."),
+        };
+
+        let theirs = match context.database.get_user(opponent_id).await {
+            Ok(Some(settings)) if settings.opted_in != Some(false) => settings,
+            Ok(_) => return ephemeral("They haven't picked a champion yet."),
+            Err(_) => return ephemeral("Failed to fetch their champion."),
+        };
+
+        let (first, second) = match (
+            champions::resolve(&own.champion),
+            champions::resolve(&theirs.champion),
+        ) {
+            (Some(first), Some(second)) => (first, second),
+            _ => return ephemeral("Invalid champion."),
+        };
+
+        let level = self.level.unwrap_or(1) as u8;
+
+        let result = match duel::simulate(first, second, level) {
+            Some(result) => result,
+            None => return ephemeral("There are no stats for one of the champions yet."),
+        };
+
+        let user_ids = [context.user_id, opponent_id];
+
+        if context
+            .database
+            .record_duel(user_ids, result.winner)
+            .await
+            .is_err()
+        {
+            return ephemeral("Failed to save the result of the duel.");
+        }
+
+        let mut records = [DuelRecord::default(); 2];
+
+        for (record, user_id) in records.iter_mut().zip(user_ids) {
+            if let Ok(fetched) = context.database.get_duel_record(user_id).await {
+                *record = fetched;
+            }
+        }
+
+        let locale = context
+            .database
+            .get_locale(context.user_id, context.guild_id)
+            .await
+            .unwrap_or_default();
+
+        let names = [context.display_name.as_str(), self.user.name.as_str()];
+
+        let fields = names
+            .into_iter()
+            .zip([first, second])
+            .zip(records)
+            .map(|((name, champion), record)| EmbedField {
+                inline: true,
+                name: format!("{} ({})", name, champions::display_name(champion, locale)),
+                value: format!(
+                    "{} wins, {} losses, {} draws",
+                    record.wins, record.losses, record.draws
+                ),
+            })
+            .collect();
+
+        let embed = Embed {
+            author: None,
+            color: None,
+            description: Some(narration(&result.lines)),
+            fields,
+            footer: Some(EmbedFooter {
+                icon_url: None,
+                proxy_icon_url: None,
+                text: format!("Auto attacks only, both at level {}", level),
+            }),
+            image: None,
+            kind: String::from("rich"),
+            provider: None,
+            thumbnail: None,
+            timestamp: None,
+            title: Some(format!(
+                "{} vs. {}",
+                champions::display_name(first, locale),
+                champions::display_name(second, locale)
+            )),
+            url: None,
+            video: None,
+        };
+
+        CallbackDataBuilder::new()
+            .content(format!(
+                "<@{}> challenged <@{}>!",
+                context.user_id, opponent_id
+            ))
+            .embeds([embed])
+            .build()
+    }
+}
+
+/// Joins the lines of a fight, cutting rounds out of the middle of long ones
+/// so the start and the finish always fit.
+fn narration(lines: &[String]) -> String {
+    let full = lines.join("\n");
+
+    if full.len() <= MAX_DESCRIPTION {
+        return full;
+    }
+
+    let mut head = Vec::new();
+    let mut tail = Vec::new();
+    let mut length = 0;
+
+    for idx in 0..lines.len() {
+        // Alternate between the start and the end of the fight.
+        let line = if idx % 2 == 0 {
+            &lines[idx / 2]
+        } else {
+            &lines[lines.len() - 1 - idx / 2]
+        };
+
+        if length + line.len() + 1 > MAX_DESCRIPTION {
+            break;
+        }
+
+        length += line.len() + 1;
+
+        if idx % 2 == 0 {
+            head.push(line.as_str());
+        } else {
+            tail.push(line.as_str());
+        }
+    }
+
+    tail.reverse();
+
+    format!("{}\n…\n{}", head.join("\n"), tail.join("\n"))
+}
+
+fn ephemeral(text: &str) -> CallbackData {
+    CallbackDataBuilder::new()
+        .content(text.to_string())
+        .flags(MessageFlags::EPHEMERAL)
+        .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{narration, MAX_DESCRIPTION};
+
+    #[test]
+    fn keeps_short_fights_whole() {
+        let lines = [String::from("Round 1"), String::from("**Garen wins!**")];
+
+        assert_eq!(narration(&lines), "Round 1\n**Garen wins!**");
+    }
+
+    #[test]
+    fn cuts_long_fights_in_the_middle() {
+        let lines: Vec<String> = (1..=500)
+            .map(|round| format!("Round {}: Garen hits Darius for 60", round))
+            .collect();
+
+        let text = narration(&lines);
+
+        assert!(text.len() <= MAX_DESCRIPTION + "\n…".len());
+        assert!(text.starts_with("Round 1: "));
+        assert!(text.ends_with("Round 500: Garen hits Darius for 60"));
+        assert!(text.contains("\n…\n"));
+    }
+}
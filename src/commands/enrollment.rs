@@ -0,0 +1,68 @@
+use twilight_interactions::command::{CommandModel, CommandOption, CreateCommand, CreateOption};
+use twilight_model::{
+    application::callback::InteractionResponse, channel::message::MessageFlags, guild::Permissions,
+};
+use twilight_util::builder::CallbackDataBuilder;
+
+use crate::Error;
+
+use super::Context;
+
+#[derive(Clone, Copy, CommandOption, CreateOption, Debug, Eq, PartialEq)]
+pub enum EnrollmentPolicy {
+    #[option(name = "Only quote people who used /optin", value = "optin")]
+    OptIn,
+    #[option(name = "Quote everyone who didn't use /optout", value = "optout")]
+    OptOut,
+}
+
+#[derive(CommandModel, CreateCommand, Debug, PartialEq, Eq)]
+#[command(
+    name = "enrollment",
+    desc = "Set whether people in this server have to opt in to be quoted"
+)]
+pub struct EnrollmentCommand {
+    #[command(desc = "Who gets quoted")]
+    pub policy: EnrollmentPolicy,
+}
+
+impl EnrollmentCommand {
+    pub async fn run(&self, context: Context) -> Result<(), Error> {
+        let text = match context.guild_id {
+            Some(guild_id) if context.permissions.contains(Permissions::MANAGE_GUILD) => {
+                let auto_enroll = self.policy == EnrollmentPolicy::OptOut;
+
+                match context
+                    .database
+                    .set_guild_auto_enroll(guild_id, auto_enroll)
+                    .await
+                {
+                    Ok(_) if auto_enroll => String::from(
+                        "Done. Everyone in this server is quoted unless they use /optout.",
+                    ),
+                    Ok(_) => String::from("Done. Only people who use /optin are quoted here."),
+                    Err(_) => String::from("Failed to update the server's enrollment policy."),
+                }
+            }
+            Some(_) => String::from("You need the Manage Server permission to do this."),
+            None => String::from("This command can only be used in servers."),
+        };
+
+        let reply = CallbackDataBuilder::new()
+            .content(text)
+            .flags(MessageFlags::EPHEMERAL)
+            .build();
+
+        context
+            .http
+            .interaction_callback(
+                context.interaction_id,
+                &context.interaction_token,
+                &InteractionResponse::ChannelMessageWithSource(reply),
+            )
+            .exec()
+            .await?;
+
+        Ok(())
+    }
+}
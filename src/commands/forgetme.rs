@@ -0,0 +1,42 @@
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_model::{application::callback::InteractionResponse, channel::message::MessageFlags};
+use twilight_util::builder::CallbackDataBuilder;
+
+use crate::Error;
+
+use super::Context;
+
+#[derive(CommandModel, CreateCommand, Debug, PartialEq, Eq)]
+#[command(
+    name = "forgetme",
+    desc = "Delete everything the bot stores about you and stop being quoted"
+)]
+pub struct ForgetmeCommand {}
+
+impl ForgetmeCommand {
+    pub async fn run(&self, context: Context) -> Result<(), Error> {
+        let text = match context.database.forget_user(context.user_id).await {
+            Ok(_) => String::from(
+                "Done. Everything about you has been deleted and you won't be quoted anymore. Use /optin if you change your mind.",
+            ),
+            Err(_) => String::from("Failed to delete your data."),
+        };
+
+        let reply = CallbackDataBuilder::new()
+            .content(text)
+            .flags(MessageFlags::EPHEMERAL)
+            .build();
+
+        context
+            .http
+            .interaction_callback(
+                context.interaction_id,
+                &context.interaction_token,
+                &InteractionResponse::ChannelMessageWithSource(reply),
+            )
+            .exec()
+            .await?;
+
+        Ok(())
+    }
+}
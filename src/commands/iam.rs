@@ -8,9 +8,12 @@ use twilight_model::{
 };
 use twilight_util::builder::CallbackDataBuilder;
 
-use crate::{champions::CHAMPIONS, Error};
+use crate::{
+    champions::{self, CHAMPIONS},
+    matching, Error,
+};
 
-use super::Context;
+use super::{champion_choices, Context};
 
 #[derive(CommandModel, CreateCommand, Debug, PartialEq, Eq)]
 #[command(
@@ -20,34 +23,62 @@ use super::Context;
 pub struct IamCommand {
     #[command(autocomplete = true, desc = "The champion you want to be quoted as")]
     pub champion: String,
+    #[command(
+        autocomplete = true,
+        desc = "The skin whose voice and icon you want to use"
+    )]
+    pub skin: Option<String>,
 }
 
 impl IamCommand {
     pub async fn run(&self, context: Context) -> Result<(), Error> {
-        let is_valid = CHAMPIONS.contains_key(&self.champion);
+        let champion = champions::resolve(&self.champion);
+
+        // `None` if the skin doesn't exist, `Some(None)` if no skin was picked.
+        let skin = match (&self.skin, champion) {
+            (Some(skin), Some(champion)) => {
+                let skin = matching::normalize(skin);
 
-        let reply = if is_valid {
-            match context
+                CHAMPIONS[champion]
+                    .skins
+                    .keys()
+                    .find(|name| matching::normalize(name) == skin)
+                    .map(|name| Some(name.as_str()))
+            }
+            (None, Some(_)) => Some(None),
+            (_, None) => None,
+        };
+
+        let text = match (champion, skin) {
+            (None, _) => match champions::suggest(&self.champion).first() {
+                Some(suggestion) => format!("Invalid champion. Did you mean {}?", suggestion),
+                None => String::from("Invalid champion."),
+            },
+            (Some(_), None) => String::from("Invalid skin."),
+            (Some(champion), Some(skin)) => match context
                 .database
-                .set_champion(context.user_id, &self.champion)
+                .set_champion(context.user_id, champion, skin)
                 .await
             {
-                Ok(_) => CallbackDataBuilder::new()
-                    .content(format!("You are now {}.", self.champion))
-                    .flags(MessageFlags::EPHEMERAL)
-                    .build(),
-                Err(_) => CallbackDataBuilder::new()
-                    .content(String::from("Failed to update your champion."))
-                    .flags(MessageFlags::EPHEMERAL)
-                    .build(),
-            }
-        } else {
-            CallbackDataBuilder::new()
-                .content(String::from("Invalid champion."))
-                .flags(MessageFlags::EPHEMERAL)
-                .build()
+                Ok(_) => {
+                    if let Some(guild_id) = context.guild_id {
+                        let _ = context
+                            .database
+                            .add_guild_member(guild_id, context.user_id)
+                            .await;
+                    }
+
+                    format!("You are now {}.", skin.unwrap_or(champion))
+                }
+                Err(_) => String::from("Failed to update your champion."),
+            },
         };
 
+        let reply = CallbackDataBuilder::new()
+            .content(text)
+            .flags(MessageFlags::EPHEMERAL)
+            .build();
+
         context
             .http
             .interaction_callback(
@@ -61,21 +92,23 @@ impl IamCommand {
         Ok(())
     }
 
-    pub async fn autocomplete(&self, context: Context) -> Result<(), Error> {
-        let matches: Vec<CommandOptionChoice> = CHAMPIONS
-            .keys()
-            .filter_map(|name| {
-                if name.starts_with(&self.champion) {
-                    Some(CommandOptionChoice::String {
-                        name: name.to_string(),
-                        value: name.to_string(),
-                    })
-                } else {
-                    None
-                }
-            })
-            .take(25)
-            .collect();
+    pub async fn autocomplete(&self, context: Context, focused: &str) -> Result<(), Error> {
+        let matches: Vec<CommandOptionChoice> = if focused == "skin" {
+            let skin = matching::normalize(self.skin.as_deref().unwrap_or_default());
+
+            champions::resolve(&self.champion)
+                .into_iter()
+                .flat_map(|champion| CHAMPIONS[champion].skins.keys())
+                .filter(|name| matching::normalize(name).contains(&skin))
+                .map(|name| CommandOptionChoice::String {
+                    name: name.to_string(),
+                    value: name.to_string(),
+                })
+                .take(25)
+                .collect()
+        } else {
+            champion_choices(&self.champion)
+        };
 
         context
             .http
@@ -0,0 +1,227 @@
+use twilight_interactions::command::{CommandModel, CommandOption, CreateCommand, CreateOption};
+use twilight_model::{
+    application::{
+        callback::{CallbackData, InteractionResponse},
+        component::{button::ButtonStyle, ActionRow, Button, Component},
+    },
+    channel::{embed::Embed, message::MessageFlags},
+};
+use twilight_util::builder::CallbackDataBuilder;
+
+use crate::{
+    champions::{self, CHAMPIONS},
+    db::Database,
+    Error,
+};
+
+use super::Context;
+
+const LIMIT: usize = 10;
+
+#[derive(Clone, Copy, CommandOption, CreateOption, Debug, Eq, PartialEq)]
+pub enum Board {
+    #[option(name = "Most quoted people", value = "quoted")]
+    Quoted,
+    #[option(name = "Most picked champions", value = "popular")]
+    Popular,
+    #[option(name = "Least picked champions", value = "unpopular")]
+    Unpopular,
+}
+
+impl Board {
+    pub const ALL: [Self; 3] = [Self::Quoted, Self::Popular, Self::Unpopular];
+
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Quoted => "quoted",
+            Self::Popular => "popular",
+            Self::Unpopular => "unpopular",
+        }
+    }
+
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Quoted => "Most quoted",
+            Self::Popular => "Most picked",
+            Self::Unpopular => "Least picked",
+        }
+    }
+
+    pub const fn title(self) -> &'static str {
+        match self {
+            Self::Quoted => "Most quoted people",
+            Self::Popular => "Most picked champions",
+            Self::Unpopular => "Least picked champions",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|board| board.as_str() == name)
+    }
+}
+
+#[derive(CommandModel, CreateCommand, Debug, PartialEq, Eq)]
+#[command(
+    name = "leaderboard",
+    desc = "View who gets quoted most and which champions are picked in this server"
+)]
+pub struct LeaderboardCommand {
+    #[command(desc = "The leaderboard to show first")]
+    pub board: Option<Board>,
+}
+
+impl LeaderboardCommand {
+    pub async fn run(&self, context: Context) -> Result<(), Error> {
+        let reply = match context.guild_id {
+            Some(guild_id) => {
+                leaderboard(
+                    &context.database,
+                    guild_id,
+                    self.board.unwrap_or(Board::Quoted),
+                )
+                .await
+            }
+            None => CallbackDataBuilder::new()
+                .content(String::from("This command can only be used in servers."))
+                .flags(MessageFlags::EPHEMERAL)
+                .build(),
+        };
+
+        context
+            .http
+            .interaction_callback(
+                context.interaction_id,
+                &context.interaction_token,
+                &InteractionResponse::ChannelMessageWithSource(reply),
+            )
+            .exec()
+            .await?;
+
+        Ok(())
+    }
+
+    /// Handles the tab buttons, whose custom ID is `leaderboard:<board>`.
+    pub async fn switch(context: Context, arguments: &str) -> Result<(), Error> {
+        let (guild_id, board) = match (context.guild_id, Board::from_name(arguments)) {
+            (Some(guild_id), Some(board)) => (guild_id, board),
+            _ => return Ok(()),
+        };
+
+        let reply = leaderboard(&context.database, guild_id, board).await;
+
+        context
+            .http
+            .interaction_callback(
+                context.interaction_id,
+                &context.interaction_token,
+                &InteractionResponse::UpdateMessage(reply),
+            )
+            .exec()
+            .await?;
+
+        Ok(())
+    }
+}
+
+async fn leaderboard(database: &Database, guild_id: i64, board: Board) -> CallbackData {
+    let description = match entries(database, guild_id, board).await {
+        Ok(lines) if lines.is_empty() => String::from("Nobody has been counted here yet."),
+        Ok(lines) => lines.join("\n"),
+        Err(_) => String::from("Failed to fetch the leaderboard."),
+    };
+
+    let embed = Embed {
+        author: None,
+        color: None,
+        description: Some(description),
+        fields: Vec::new(),
+        footer: None,
+        image: None,
+        kind: String::from("rich"),
+        provider: None,
+        thumbnail: None,
+        timestamp: None,
+        title: Some(board.title().to_string()),
+        url: None,
+        video: None,
+    };
+
+    let tabs = Component::ActionRow(ActionRow {
+        components: Board::ALL
+            .into_iter()
+            .map(|tab| {
+                Component::Button(Button {
+                    custom_id: Some(format!("leaderboard:{}", tab.as_str())),
+                    disabled: tab == board,
+                    emoji: None,
+                    label: Some(tab.label().to_string()),
+                    style: if tab == board {
+                        ButtonStyle::Primary
+                    } else {
+                        ButtonStyle::Secondary
+                    },
+                    url: None,
+                })
+            })
+            .collect(),
+    });
+
+    CallbackDataBuilder::new()
+        .embeds([embed])
+        .components([tabs])
+        .build()
+}
+
+async fn entries(
+    database: &Database,
+    guild_id: i64,
+    board: Board,
+) -> Result<Vec<String>, sqlx::Error> {
+    let locale = database
+        .get_guild_settings(guild_id)
+        .await
+        .ok()
+        .and_then(|settings| settings.locale)
+        .unwrap_or_default();
+
+    let lines = match board {
+        Board::Quoted => database
+            .get_quote_leaders(guild_id, LIMIT as i64)
+            .await?
+            .into_iter()
+            .enumerate()
+            .map(|(idx, (user_id, count))| format!("{}. <@{}>: {} quotes", idx + 1, user_id, count))
+            .collect(),
+        Board::Popular | Board::Unpopular => {
+            let picks = database.get_champion_picks(guild_id).await?;
+
+            let mut picked: Vec<(&str, i64)> = CHAMPIONS
+                .keys()
+                .map(|name| (name.as_str(), picks.get(name).copied().unwrap_or(0)))
+                .collect();
+
+            if board == Board::Popular {
+                picked.retain(|(_, count)| *count > 0);
+                picked.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+            } else {
+                picked.sort_by(|a, b| a.1.cmp(&b.1).then(a.0.cmp(b.0)));
+            }
+
+            picked
+                .into_iter()
+                .take(LIMIT)
+                .enumerate()
+                .map(|(idx, (name, count))| {
+                    format!(
+                        "{}. {}: {} picks",
+                        idx + 1,
+                        champions::display_name(name, locale),
+                        count
+                    )
+                })
+                .collect()
+        }
+    };
+
+    Ok(lines)
+}
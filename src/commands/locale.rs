@@ -0,0 +1,67 @@
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_model::{
+    application::callback::InteractionResponse, channel::message::MessageFlags, guild::Permissions,
+};
+use twilight_util::builder::CallbackDataBuilder;
+
+use crate::{ddragon::Locale, Error};
+
+use super::Context;
+
+#[derive(CommandModel, CreateCommand, Debug, PartialEq, Eq)]
+#[command(name = "locale", desc = "Set the language quotes are in")]
+pub struct LocaleCommand {
+    #[command(desc = "The language to quote in")]
+    pub language: Locale,
+    #[command(desc = "Change the default for the whole server instead of just for you")]
+    pub server: Option<bool>,
+}
+
+impl LocaleCommand {
+    pub async fn run(&self, context: Context) -> Result<(), Error> {
+        let language = self.language.name();
+
+        let text = if self.server.unwrap_or(false) {
+            match context.guild_id {
+                Some(guild_id) if context.permissions.contains(Permissions::MANAGE_GUILD) => {
+                    match context
+                        .database
+                        .set_guild_locale(guild_id, self.language)
+                        .await
+                    {
+                        Ok(_) => format!("Done. Quotes are now in {} in this server.", language),
+                        Err(_) => String::from("Failed to update the server's language."),
+                    }
+                }
+                Some(_) => String::from("You need the Manage Server permission to do this."),
+                None => String::from("This command can only be used in servers."),
+            }
+        } else {
+            match context
+                .database
+                .set_locale(context.user_id, self.language)
+                .await
+            {
+                Ok(_) => format!("Done. Your quotes are now in {}.", language),
+                Err(_) => String::from("Failed to update your language."),
+            }
+        };
+
+        let reply = CallbackDataBuilder::new()
+            .content(text)
+            .flags(MessageFlags::EPHEMERAL)
+            .build();
+
+        context
+            .http
+            .interaction_callback(
+                context.interaction_id,
+                &context.interaction_token,
+                &InteractionResponse::ChannelMessageWithSource(reply),
+            )
+            .exec()
+            .await?;
+
+        Ok(())
+    }
+}
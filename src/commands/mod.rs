@@ -1,25 +1,187 @@
+pub mod audio;
+pub mod champion;
 pub mod champions;
+pub mod chaos;
+pub mod config;
+pub mod context_menus;
+pub mod duel;
+pub mod enrollment;
+pub mod forgetme;
 pub mod iam;
+pub mod leaderboard;
+pub mod locale;
+pub mod mydata;
+pub mod optin;
+pub mod optout;
+pub mod qotd;
+pub mod quote;
+pub mod search;
 pub mod setrate;
+pub mod setweight;
+pub mod stats;
+pub mod trivia;
 pub mod whoami;
 pub mod whois;
 
 use std::sync::Arc;
 
+pub use audio::AudioCommand;
+pub use champion::ChampionCommand;
 pub use champions::ChampionsCommand;
+pub use chaos::ChaosCommand;
+pub use config::ConfigCommand;
+pub use context_menus::{AnswerCommand, WhichChampionCommand};
+pub use duel::DuelCommand;
+pub use enrollment::EnrollmentCommand;
+pub use forgetme::ForgetmeCommand;
 pub use iam::IamCommand;
+pub use leaderboard::LeaderboardCommand;
+pub use locale::LocaleCommand;
+pub use mydata::MydataCommand;
+pub use optin::OptinCommand;
+pub use optout::OptoutCommand;
+pub use qotd::QotdCommand;
+pub use quote::QuoteCommand;
+pub use search::SearchCommand;
 pub use setrate::SetrateCommand;
+pub use setweight::SetweightCommand;
+pub use stats::StatsCommand;
+pub use trivia::TriviaCommand;
 use twilight_http::Client;
-use twilight_model::id::InteractionId;
+use twilight_model::{
+    application::command::CommandOptionChoice,
+    channel::embed::{Embed, EmbedAuthor, EmbedField, EmbedThumbnail},
+    guild::Permissions,
+    id::{ChannelId, InteractionId},
+};
 pub use whoami::WhoamiCommand;
 pub use whois::WhoisCommand;
 
-use crate::db::Database;
+use crate::{
+    champions::CHAMPIONS,
+    db::{Database, UserSettings},
+    ddragon::Locale,
+    trivia::Games,
+    webhooks::Webhooks,
+};
 
 pub struct Context {
     pub http: Arc<Client>,
     pub database: Arc<Database>,
+    pub webhooks: Arc<Webhooks>,
+    pub trivia: Arc<Games>,
     pub user_id: i64,
+    pub display_name: String,
+    pub guild_id: Option<i64>,
+    pub channel_id: ChannelId,
+    pub permissions: Permissions,
     pub interaction_id: InteractionId,
     pub interaction_token: String,
 }
+
+/// Autocomplete choices for a champion option.
+pub fn champion_choices(input: &str) -> Vec<CommandOptionChoice> {
+    crate::champions::suggest(input)
+        .into_iter()
+        .map(|name| CommandOptionChoice::String {
+            name: name.to_string(),
+            value: name.to_string(),
+        })
+        .take(25)
+        .collect()
+}
+
+/// Why quotes can't be posted to a channel, if the server turned quoting off
+/// or ignores the channel.
+async fn quoting_blocked(context: &Context, channel_id: ChannelId) -> Option<&'static str> {
+    let settings = context
+        .database
+        .get_guild_settings(context.guild_id?)
+        .await
+        .unwrap_or_default();
+
+    if !settings.enabled {
+        Some("Quoting is turned off in this server.")
+    } else if context.webhooks.is_ignored(&settings.ignored, channel_id).await {
+        Some("Nobody is quoted in this channel.")
+    } else {
+        None
+    }
+}
+
+/// The profile shown by /whoami and /whois, with the rate someone is quoted
+/// at in the current channel.
+pub fn profile_embed(
+    name: &str,
+    settings: &UserSettings,
+    locale: Locale,
+    rate: i64,
+    quote_count: i64,
+) -> Embed {
+    let data = CHAMPIONS.get(&settings.champion);
+    let skin = settings
+        .skin
+        .as_ref()
+        .and_then(|skin| data.and_then(|data| data.skins.get_key_value(skin)));
+
+    let tags = match data {
+        Some(data) if !data.tags.is_empty() => data.tags.join(", "),
+        _ => String::from("None"),
+    };
+
+    let picked_at = match settings.picked_at {
+        Some(timestamp) => format!("<t:{}:R>", timestamp),
+        None => String::from("Unknown"),
+    };
+
+    let rate = if rate == settings.rate {
+        format!("{}%", rate)
+    } else {
+        format!("{}% here ({}% global)", rate, settings.rate)
+    };
+
+    let field = |name: &str, value: String| EmbedField {
+        inline: true,
+        name: name.to_string(),
+        value,
+    };
+
+    Embed {
+        author: Some(EmbedAuthor {
+            icon_url: None,
+            name: name.to_string(),
+            proxy_icon_url: None,
+            url: None,
+        }),
+        color: None,
+        description: data
+            .map(|data| data.title.clone())
+            .filter(|title| !title.is_empty()),
+        fields: vec![
+            field("Classes", tags),
+            field("Quote rate", rate),
+            field("Picked", picked_at),
+            field("Times quoted", quote_count.to_string()),
+        ],
+        footer: None,
+        image: None,
+        kind: String::from("rich"),
+        provider: None,
+        thumbnail: skin
+            .map(|(_, skin)| &skin.icon)
+            .or_else(|| data.map(|data| &data.icon))
+            .map(|icon| EmbedThumbnail {
+                height: None,
+                proxy_url: None,
+                url: icon.clone(),
+                width: None,
+            }),
+        timestamp: None,
+        title: Some(match skin {
+            Some((skin, _)) => skin.clone(),
+            None => crate::champions::display_name(&settings.champion, locale).to_string(),
+        }),
+        url: None,
+        video: None,
+    }
+}
@@ -0,0 +1,58 @@
+use twilight_http::request::AttachmentFile;
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_model::{application::callback::InteractionResponse, channel::message::MessageFlags};
+use twilight_util::builder::CallbackDataBuilder;
+
+use crate::Error;
+
+use super::Context;
+
+#[derive(CommandModel, CreateCommand, Debug, PartialEq, Eq)]
+#[command(name = "mydata", desc = "Download everything the bot stores about you")]
+pub struct MydataCommand {}
+
+impl MydataCommand {
+    pub async fn run(&self, context: Context) -> Result<(), Error> {
+        // Interaction responses can't carry files, so the export is attached
+        // to the deferred response afterwards.
+        let reply = CallbackDataBuilder::new()
+            .flags(MessageFlags::EPHEMERAL)
+            .build();
+
+        context
+            .http
+            .interaction_callback(
+                context.interaction_id,
+                &context.interaction_token,
+                &InteractionResponse::DeferredChannelMessageWithSource(reply),
+            )
+            .exec()
+            .await?;
+
+        let data = match context.database.export_user(context.user_id).await {
+            Ok(data) => serde_json::to_vec_pretty(&data).unwrap_or_default(),
+            Err(_) => {
+                if let Ok(request) = context
+                    .http
+                    .update_interaction_original(&context.interaction_token)?
+                    .content(Some("Failed to export your data."))
+                {
+                    request.exec().await?;
+                }
+
+                return Ok(());
+            }
+        };
+
+        let attachments = [AttachmentFile::from_bytes("mydata.json", &data)];
+
+        context
+            .http
+            .update_interaction_original(&context.interaction_token)?
+            .attach(&attachments)
+            .exec()
+            .await?;
+
+        Ok(())
+    }
+}
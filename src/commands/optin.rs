@@ -0,0 +1,46 @@
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_model::{application::callback::InteractionResponse, channel::message::MessageFlags};
+use twilight_util::builder::CallbackDataBuilder;
+
+use crate::Error;
+
+use super::Context;
+
+#[derive(CommandModel, CreateCommand, Debug, PartialEq, Eq)]
+#[command(name = "optin", desc = "Let the bot quote you as your champion")]
+pub struct OptinCommand {}
+
+impl OptinCommand {
+    pub async fn run(&self, context: Context) -> Result<(), Error> {
+        let text = match context.database.set_opted_in(context.user_id, true).await {
+            Ok(_) => {
+                if let Some(guild_id) = context.guild_id {
+                    let _ = context
+                        .database
+                        .add_guild_member(guild_id, context.user_id)
+                        .await;
+                }
+
+                String::from("Done. You will now be quoted as your champion.")
+            }
+            Err(_) => String::from("Failed to opt in."),
+        };
+
+        let reply = CallbackDataBuilder::new()
+            .content(text)
+            .flags(MessageFlags::EPHEMERAL)
+            .build();
+
+        context
+            .http
+            .interaction_callback(
+                context.interaction_id,
+                &context.interaction_token,
+                &InteractionResponse::ChannelMessageWithSource(reply),
+            )
+            .exec()
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,37 @@
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_model::{application::callback::InteractionResponse, channel::message::MessageFlags};
+use twilight_util::builder::CallbackDataBuilder;
+
+use crate::Error;
+
+use super::Context;
+
+#[derive(CommandModel, CreateCommand, Debug, PartialEq, Eq)]
+#[command(name = "optout", desc = "Stop the bot from quoting you")]
+pub struct OptoutCommand {}
+
+impl OptoutCommand {
+    pub async fn run(&self, context: Context) -> Result<(), Error> {
+        let text = match context.database.set_opted_in(context.user_id, false).await {
+            Ok(_) => String::from("Done. You won't be quoted anymore."),
+            Err(_) => String::from("Failed to opt out."),
+        };
+
+        let reply = CallbackDataBuilder::new()
+            .content(text)
+            .flags(MessageFlags::EPHEMERAL)
+            .build();
+
+        context
+            .http
+            .interaction_callback(
+                context.interaction_id,
+                &context.interaction_token,
+                &InteractionResponse::ChannelMessageWithSource(reply),
+            )
+            .exec()
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,181 @@
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_model::{
+    application::{
+        callback::InteractionResponse, interaction::application_command::InteractionChannel,
+    },
+    channel::message::MessageFlags,
+    guild::Permissions,
+};
+use twilight_util::builder::CallbackDataBuilder;
+
+use crate::{champions, qotd, Error};
+
+use super::Context;
+
+#[derive(CommandModel, CreateCommand, Debug, PartialEq, Eq)]
+#[command(name = "qotd", desc = "Manage the daily quote of the day")]
+pub enum QotdCommand {
+    #[command(name = "subscribe")]
+    Subscribe(QotdSubscribeCommand),
+    #[command(name = "unsubscribe")]
+    Unsubscribe(QotdUnsubscribeCommand),
+    #[command(name = "preview")]
+    Preview(QotdPreviewCommand),
+}
+
+#[derive(CommandModel, CreateCommand, Debug, PartialEq, Eq)]
+#[command(
+    name = "subscribe",
+    desc = "Post a quote of the day in a channel every day"
+)]
+pub struct QotdSubscribeCommand {
+    #[command(desc = "The hour to post at", min_value = 0, max_value = 23)]
+    pub hour: i64,
+    #[command(desc = "The minute to post at", min_value = 0, max_value = 59)]
+    pub minute: Option<i64>,
+    #[command(desc = "Your timezone's offset from UTC in hours, e.g. 1 or -5")]
+    pub utc_offset: Option<i64>,
+    #[command(
+        desc = "The channel to post in instead of this one",
+        channel_types = "guild_text guild_news"
+    )]
+    pub channel: Option<InteractionChannel>,
+}
+
+#[derive(CommandModel, CreateCommand, Debug, PartialEq, Eq)]
+#[command(name = "unsubscribe", desc = "Stop posting a quote of the day")]
+pub struct QotdUnsubscribeCommand {
+    #[command(
+        desc = "The channel to stop posting in instead of this one",
+        channel_types = "guild_text guild_news"
+    )]
+    pub channel: Option<InteractionChannel>,
+}
+
+#[derive(CommandModel, CreateCommand, Debug, PartialEq, Eq)]
+#[command(name = "preview", desc = "View today's quote of the day")]
+pub struct QotdPreviewCommand {}
+
+impl QotdCommand {
+    pub async fn run(&self, context: Context) -> Result<(), Error> {
+        let text = match self {
+            Self::Subscribe(command) => command.run(&context).await,
+            Self::Unsubscribe(command) => command.run(&context).await,
+            Self::Preview(command) => command.run(&context).await,
+        };
+
+        let reply = CallbackDataBuilder::new()
+            .content(text)
+            .flags(MessageFlags::EPHEMERAL)
+            .build();
+
+        context
+            .http
+            .interaction_callback(
+                context.interaction_id,
+                &context.interaction_token,
+                &InteractionResponse::ChannelMessageWithSource(reply),
+            )
+            .exec()
+            .await?;
+
+        Ok(())
+    }
+}
+
+impl QotdSubscribeCommand {
+    async fn run(&self, context: &Context) -> String {
+        let guild_id = match context.guild_id {
+            Some(guild_id) if context.permissions.contains(Permissions::MANAGE_GUILD) => guild_id,
+            Some(_) => return String::from("You need the Manage Server permission to do this."),
+            None => return String::from("This command can only be used in servers."),
+        };
+
+        let minute = self.minute.unwrap_or(0);
+        let utc_offset = self.utc_offset.unwrap_or(0);
+
+        if !(-12..=14).contains(&utc_offset) {
+            return String::from("The UTC offset has to be between -12 and 14.");
+        }
+
+        let channel_id = self
+            .channel
+            .as_ref()
+            .map_or(context.channel_id, |channel| channel.id);
+
+        let utc_minute = (self.hour * 60 + minute - utc_offset * 60).rem_euclid(24 * 60);
+
+        // Don't post right away when subscribing after today's time.
+        let (today, now) = qotd::now();
+        let last_posted = if utc_minute <= now { Some(today) } else { None };
+
+        match context
+            .database
+            .subscribe_qotd(channel_id.get() as i64, guild_id, utc_minute, last_posted)
+            .await
+        {
+            Ok(_) => format!(
+                "Done. <#{}> will get a quote of the day at {:02}:{:02} (UTC{:+}) every day.",
+                channel_id, self.hour, minute, utc_offset
+            ),
+            Err(_) => String::from("Failed to subscribe to the quote of the day."),
+        }
+    }
+}
+
+impl QotdUnsubscribeCommand {
+    async fn run(&self, context: &Context) -> String {
+        match context.guild_id {
+            Some(_) if context.permissions.contains(Permissions::MANAGE_GUILD) => {}
+            Some(_) => return String::from("You need the Manage Server permission to do this."),
+            None => return String::from("This command can only be used in servers."),
+        };
+
+        let channel_id = self
+            .channel
+            .as_ref()
+            .map_or(context.channel_id, |channel| channel.id);
+
+        match context
+            .database
+            .unsubscribe_qotd(channel_id.get() as i64)
+            .await
+        {
+            Ok(true) => format!(
+                "Done. <#{}> won't get a quote of the day anymore.",
+                channel_id
+            ),
+            Ok(false) => format!(
+                "<#{}> isn't subscribed to the quote of the day.",
+                channel_id
+            ),
+            Err(_) => String::from("Failed to unsubscribe from the quote of the day."),
+        }
+    }
+}
+
+impl QotdPreviewCommand {
+    async fn run(&self, context: &Context) -> String {
+        let locale = match context.guild_id {
+            Some(guild_id) => context
+                .database
+                .get_guild_settings(guild_id)
+                .await
+                .ok()
+                .and_then(|settings| settings.locale)
+                .unwrap_or_default(),
+            None => Default::default(),
+        };
+
+        let (today, _) = qotd::now();
+
+        match qotd::pick(today, locale) {
+            Some((champion, voice, quote)) => format!(
+                "**{}**: {}",
+                champions::display_name(champion, voice.locale),
+                quote.text
+            ),
+            None => String::from("There is no quote of the day today."),
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use rand::{prelude::IteratorRandom, thread_rng};
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_model::{
+    application::callback::{Autocomplete, InteractionResponse},
+    channel::message::MessageFlags,
+};
+use twilight_util::builder::CallbackDataBuilder;
+
+use crate::{champions, db::QuoteTrigger, Error};
+
+use super::{champion_choices, quoting_blocked, Context};
+
+#[derive(CommandModel, CreateCommand, Debug, PartialEq, Eq)]
+#[command(name = "quote", desc = "Quote your champion or another one right now")]
+pub struct QuoteCommand {
+    #[command(
+        autocomplete = true,
+        desc = "The champion to quote instead of your own"
+    )]
+    pub champion: Option<String>,
+    #[command(desc = "Only pick a quote containing this text")]
+    pub contains: Option<String>,
+}
+
+impl QuoteCommand {
+    pub async fn run(&self, context: Context) -> Result<(), Error> {
+        // Sending the quote can take longer than an interaction may go
+        // unanswered, so the reply is filled in afterwards.
+        let reply = CallbackDataBuilder::new()
+            .flags(MessageFlags::EPHEMERAL)
+            .build();
+
+        context
+            .http
+            .interaction_callback(
+                context.interaction_id,
+                &context.interaction_token,
+                &InteractionResponse::DeferredChannelMessageWithSource(reply),
+            )
+            .exec()
+            .await?;
+
+        let text = self.quote(&context).await;
+
+        if let Ok(request) = context
+            .http
+            .update_interaction_original(&context.interaction_token)?
+            .content(Some(&text))
+        {
+            request.exec().await?;
+        }
+
+        Ok(())
+    }
+
+    async fn quote(&self, context: &Context) -> String {
+        if let Some(reason) = quoting_blocked(context, context.channel_id).await {
+            return reason.to_string();
+        }
+
+        let locale = context
+            .database
+            .get_locale(context.user_id, context.guild_id)
+            .await
+            .unwrap_or_default();
+
+        let voice = match &self.champion {
+            Some(champion) => champions::resolve(champion)
+                .and_then(|champion| champions::voice(champion, None, locale)),
+            None => match context.database.get_user(context.user_id).await {
+                Ok(Some(settings)) => {
+                    champions::voice(&settings.champion, settings.skin.as_deref(), locale)
+                }
+                _ => None,
+            },
+        };
+
+        match voice {
+            Some(voice) => {
+                let contains = self.contains.as_deref().unwrap_or_default().to_lowercase();
+
+                let quote = {
+                    let mut rng = thread_rng();
+
+                    voice
+                        .quotes
+                        .iter()
+                        .filter(|quote| quote.text.to_lowercase().contains(&contains))
+                        .choose(&mut rng)
+                };
+
+                match quote {
+                    Some(quote) => match context
+                        .webhooks
+                        .execute(
+                            context.channel_id,
+                            &context.display_name,
+                            voice.icon,
+                            &quote.text,
+                            &[],
+                        )
+                        .await
+                    {
+                        Ok(true) => {
+                            let _ = context
+                                .database
+                                .log_quote(
+                                    context.user_id,
+                                    context.guild_id,
+                                    context.channel_id.get() as i64,
+                                    &voice,
+                                    &quote.text,
+                                    QuoteTrigger::Command,
+                                )
+                                .await;
+
+                            String::from("Sent.")
+                        }
+                        Ok(false) => String::from("Quotes can't be posted in this channel."),
+                        Err(_) => String::from("Failed to send the quote."),
+                    },
+                    None => String::from("No quote matches that."),
+                }
+            }
+            None if self.champion.is_some() => String::from("Invalid champion."),
+            None => String::from("You don't have a champion yet, pick one or use /optin."),
+        }
+    }
+
+    pub async fn autocomplete(&self, context: Context) -> Result<(), Error> {
+        let matches = champion_choices(self.champion.as_deref().unwrap_or_default());
+
+        context
+            .http
+            .interaction_callback(
+                context.interaction_id,
+                &context.interaction_token,
+                &InteractionResponse::Autocomplete(Autocomplete { choices: matches }),
+            )
+            .exec()
+            .await?;
+
+        Ok(())
+    }
+}
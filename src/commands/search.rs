@@ -0,0 +1,137 @@
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_model::{
+    application::{
+        callback::{CallbackData, InteractionResponse},
+        component::{button::ButtonStyle, ActionRow, Button, Component},
+    },
+    channel::{
+        embed::{Embed, EmbedFooter},
+        message::MessageFlags,
+    },
+};
+use twilight_util::builder::CallbackDataBuilder;
+
+use crate::{champions, Error};
+
+use super::Context;
+
+const PAGE_SIZE: usize = 10;
+// Custom IDs are limited to 100 characters and have to fit the page too.
+const MAX_QUERY_LENGTH: usize = 80;
+
+#[derive(CommandModel, CreateCommand, Debug, PartialEq, Eq)]
+#[command(name = "search", desc = "Find out which champions say something")]
+pub struct SearchCommand {
+    #[command(desc = "The words to look for")]
+    pub query: String,
+}
+
+impl SearchCommand {
+    pub async fn run(&self, context: Context) -> Result<(), Error> {
+        let query: String = self.query.chars().take(MAX_QUERY_LENGTH).collect();
+
+        context
+            .http
+            .interaction_callback(
+                context.interaction_id,
+                &context.interaction_token,
+                &InteractionResponse::ChannelMessageWithSource(results(&query, 0)),
+            )
+            .exec()
+            .await?;
+
+        Ok(())
+    }
+
+    /// Handles the Previous and Next buttons, whose custom ID is
+    /// `search:<page>:<query>`.
+    pub async fn paginate(context: Context, arguments: &str) -> Result<(), Error> {
+        let (page, query) = match arguments.split_once(':') {
+            Some((page, query)) => (page.parse().unwrap_or(0), query),
+            None => return Ok(()),
+        };
+
+        context
+            .http
+            .interaction_callback(
+                context.interaction_id,
+                &context.interaction_token,
+                &InteractionResponse::UpdateMessage(results(query, page)),
+            )
+            .exec()
+            .await?;
+
+        Ok(())
+    }
+}
+
+fn results(query: &str, page: usize) -> CallbackData {
+    let matches = champions::search(query);
+
+    let pages = matches.len().div_ceil(PAGE_SIZE);
+    let page = page.min(pages.saturating_sub(1));
+
+    let description = if matches.is_empty() {
+        String::from("No quotes match that.")
+    } else {
+        matches
+            .iter()
+            .skip(page * PAGE_SIZE)
+            .take(PAGE_SIZE)
+            .map(|(champion, quote)| format!("**{}**: {}", champion, quote.text))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    let embed = Embed {
+        author: None,
+        color: None,
+        description: Some(description),
+        fields: Vec::new(),
+        footer: Some(EmbedFooter {
+            icon_url: None,
+            proxy_icon_url: None,
+            text: format!(
+                "Page {} of {} · {} quotes",
+                page + 1,
+                pages.max(1),
+                matches.len()
+            ),
+        }),
+        image: None,
+        kind: String::from("rich"),
+        provider: None,
+        thumbnail: None,
+        timestamp: None,
+        title: Some(format!("Quotes matching \"{}\"", query)),
+        url: None,
+        video: None,
+    };
+
+    let buttons = Component::ActionRow(ActionRow {
+        components: vec![
+            Component::Button(Button {
+                custom_id: Some(format!("search:{}:{}", page.saturating_sub(1), query)),
+                disabled: page == 0,
+                emoji: None,
+                label: Some(String::from("Previous")),
+                style: ButtonStyle::Secondary,
+                url: None,
+            }),
+            Component::Button(Button {
+                custom_id: Some(format!("search:{}:{}", page + 1, query)),
+                disabled: page + 1 >= pages,
+                emoji: None,
+                label: Some(String::from("Next")),
+                style: ButtonStyle::Secondary,
+                url: None,
+            }),
+        ],
+    });
+
+    CallbackDataBuilder::new()
+        .embeds([embed])
+        .components([buttons])
+        .flags(MessageFlags::EPHEMERAL)
+        .build()
+}
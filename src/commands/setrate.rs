@@ -1,11 +1,21 @@
-use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_interactions::command::{CommandModel, CommandOption, CreateCommand, CreateOption};
 use twilight_model::{application::callback::InteractionResponse, channel::message::MessageFlags};
 use twilight_util::builder::CallbackDataBuilder;
 
-use crate::Error;
+use crate::{db::RateScope, Error};
 
 use super::Context;
 
+#[derive(Clone, Copy, CommandOption, CreateOption, Debug, Eq, PartialEq)]
+pub enum Scope {
+    #[option(name = "Everywhere", value = "global")]
+    Global,
+    #[option(name = "This server", value = "server")]
+    Server,
+    #[option(name = "This channel", value = "channel")]
+    Channel,
+}
+
 #[derive(CommandModel, CreateCommand, Debug, PartialEq, Eq)]
 #[command(
     name = "setrate",
@@ -13,22 +23,32 @@ use super::Context;
 )]
 pub struct SetrateCommand {
     #[command(
-        desc = "Percentage of messages to quote you on",
+        desc = "Percentage of messages to quote you on, leave out to clear a server or channel rate",
         min_value = 0,
         max_value = 100
     )]
-    pub percentage: i64,
+    pub percentage: Option<i64>,
+    #[command(desc = "Where this rate applies, defaults to everywhere")]
+    pub scope: Option<Scope>,
 }
 
 impl SetrateCommand {
     pub async fn run(&self, context: Context) -> Result<(), Error> {
-        let text = match context
-            .database
-            .set_rate(context.user_id, self.percentage)
-            .await
-        {
-            Ok(_) => format!("Done. Your quote rate is now {}%", self.percentage),
-            Err(_) => String::from("Failed to update your quote rate."),
+        let text = match (self.scope.unwrap_or(Scope::Global), context.guild_id) {
+            (Scope::Global, _) => self.set_global(&context).await,
+            (Scope::Server, Some(guild_id)) => {
+                self.set_override(&context, RateScope::Guild, guild_id)
+                    .await
+            }
+            (Scope::Channel, Some(_)) => {
+                self.set_override(
+                    &context,
+                    RateScope::Channel,
+                    context.channel_id.get() as i64,
+                )
+                .await
+            }
+            (_, None) => String::from("This command can only be used in servers."),
         };
 
         let reply = CallbackDataBuilder::new()
@@ -48,4 +68,121 @@ impl SetrateCommand {
 
         Ok(())
     }
+
+    async fn set_global(&self, context: &Context) -> String {
+        let percentage = match self.percentage {
+            Some(percentage) => percentage,
+            None => return String::from("Pick a percentage to set your quote rate everywhere."),
+        };
+
+        if context
+            .database
+            .set_rate(context.user_id, percentage)
+            .await
+            .is_err()
+        {
+            return String::from("Failed to update your quote rate.");
+        }
+
+        let text = format!("Done. Your quote rate is now {}%", percentage);
+
+        let overrides = context
+            .database
+            .get_rate_overrides(context.user_id)
+            .await
+            .unwrap_or_default();
+
+        let find = |scope: RateScope, scope_id: i64| {
+            overrides
+                .iter()
+                .find(|(other_scope, other_id, _)| *other_scope == scope && *other_id == scope_id)
+        };
+
+        let here = find(RateScope::Channel, context.channel_id.get() as i64).or_else(|| {
+            context
+                .guild_id
+                .and_then(|guild_id| find(RateScope::Guild, guild_id))
+        });
+
+        match here {
+            Some((scope, _, rate)) => format!(
+                "{}, but your {} rate of {}% still applies here",
+                text,
+                scope_name(*scope),
+                rate
+            ),
+            None if !overrides.is_empty() => {
+                format!("{}, except where you set a server or channel rate", text)
+            }
+            None => text,
+        }
+    }
+
+    async fn set_override(&self, context: &Context, scope: RateScope, scope_id: i64) -> String {
+        let database = &context.database;
+
+        let percentage = match self.percentage {
+            Some(percentage) => percentage,
+            None => {
+                return match database
+                    .remove_rate_override(context.user_id, scope, scope_id)
+                    .await
+                {
+                    Ok(true) => format!(
+                        "Done. You no longer have a quote rate for this {}",
+                        scope_name(scope)
+                    ),
+                    Ok(false) => format!(
+                        "You don't have a quote rate for this {}.",
+                        scope_name(scope)
+                    ),
+                    Err(_) => String::from("Failed to update your quote rate."),
+                };
+            }
+        };
+
+        if database
+            .set_rate_override(context.user_id, scope, scope_id, percentage)
+            .await
+            .is_err()
+        {
+            return String::from("Failed to update your quote rate.");
+        }
+
+        let text = format!(
+            "Done. Your quote rate in this {} is now {}%",
+            scope_name(scope),
+            percentage
+        );
+
+        // A channel rate wins over the server's.
+        let channel_rate = match scope {
+            RateScope::Guild => database
+                .get_rate_overrides(context.user_id)
+                .await
+                .unwrap_or_default()
+                .into_iter()
+                .find(|(other_scope, other_id, _)| {
+                    *other_scope == RateScope::Channel
+                        && *other_id == context.channel_id.get() as i64
+                })
+                .map(|(_, _, rate)| rate),
+            RateScope::Channel => None,
+        };
+
+        match channel_rate {
+            Some(rate) => format!(
+                "{}, but your channel rate of {}% still applies here",
+                text, rate
+            ),
+            None => text,
+        }
+    }
+}
+
+const fn scope_name(scope: RateScope) -> &'static str {
+    match scope {
+        RateScope::Guild => "server",
+        RateScope::Channel => "channel",
+    }
 }
@@ -0,0 +1,65 @@
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_model::{
+    application::callback::InteractionResponse, channel::message::MessageFlags, guild::Permissions,
+};
+use twilight_util::builder::CallbackDataBuilder;
+
+use crate::{ddragon::QuoteCategory, Error};
+
+use super::Context;
+
+#[derive(CommandModel, CreateCommand, Debug, PartialEq, Eq)]
+#[command(
+    name = "setweight",
+    desc = "Set how early a category of voice lines comes up in this server"
+)]
+pub struct SetweightCommand {
+    #[command(desc = "The category of voice lines")]
+    pub category: QuoteCategory,
+    #[command(
+        desc = "Relative weight of the category, 0 disables it",
+        min_value = 0,
+        max_value = 10
+    )]
+    pub weight: i64,
+}
+
+impl SetweightCommand {
+    pub async fn run(&self, context: Context) -> Result<(), Error> {
+        let text = match context.guild_id {
+            Some(guild_id) if context.permissions.contains(Permissions::MANAGE_GUILD) => {
+                match context
+                    .database
+                    .set_category_weight(guild_id, self.category, self.weight)
+                    .await
+                {
+                    Ok(_) => format!(
+                        "Done. The weight of {} lines is now {}.",
+                        self.category.as_str(),
+                        self.weight
+                    ),
+                    Err(_) => String::from("Failed to update the category weight."),
+                }
+            }
+            Some(_) => String::from("You need the Manage Server permission to do this."),
+            None => String::from("This command can only be used in servers."),
+        };
+
+        let reply = CallbackDataBuilder::new()
+            .content(text)
+            .flags(MessageFlags::EPHEMERAL)
+            .build();
+
+        context
+            .http
+            .interaction_callback(
+                context.interaction_id,
+                &context.interaction_token,
+                &InteractionResponse::ChannelMessageWithSource(reply),
+            )
+            .exec()
+            .await?;
+
+        Ok(())
+    }
+}
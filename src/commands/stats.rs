@@ -0,0 +1,178 @@
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_model::{
+    application::callback::InteractionResponse,
+    channel::{
+        embed::{Embed, EmbedField, EmbedFooter},
+        message::MessageFlags,
+    },
+    user::User,
+};
+use twilight_util::builder::CallbackDataBuilder;
+
+use crate::{champions, db::QuoteStats, ddragon::Locale, Error};
+
+use super::Context;
+
+const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+#[derive(CommandModel, CreateCommand, Debug, PartialEq, Eq)]
+#[command(
+    name = "stats",
+    desc = "View how often someone or this server got quoted"
+)]
+pub struct StatsCommand {
+    #[command(desc = "The person whose stats you want to see, defaults to you")]
+    pub user: Option<User>,
+    #[command(desc = "Show stats for the whole server instead")]
+    pub server: Option<bool>,
+}
+
+impl StatsCommand {
+    pub async fn run(&self, context: Context) -> Result<(), Error> {
+        let locale = context
+            .database
+            .get_locale(context.user_id, context.guild_id)
+            .await
+            .unwrap_or_default();
+
+        // Other people's stats are limited to the server they are looked up in.
+        let (title, user_id, guild_id) = match (&self.user, self.server.unwrap_or(false)) {
+            (_, true) => (
+                String::from("Quotes in this server"),
+                None,
+                context.guild_id,
+            ),
+            (Some(user), false) if user.id.get() as i64 != context.user_id => (
+                format!("Quotes of {}", user.name),
+                Some(user.id.get() as i64),
+                context.guild_id,
+            ),
+            _ => (
+                format!("Quotes of {}", context.display_name),
+                Some(context.user_id),
+                None,
+            ),
+        };
+
+        let reply = if guild_id.is_none() && user_id != Some(context.user_id) {
+            CallbackDataBuilder::new()
+                .content(String::from("This command can only be used in servers."))
+                .flags(MessageFlags::EPHEMERAL)
+                .build()
+        } else {
+            match context.database.get_quote_stats(user_id, guild_id).await {
+                Ok(stats) if stats.total == 0 => CallbackDataBuilder::new()
+                    .content(String::from("No quotes recorded yet."))
+                    .flags(MessageFlags::EPHEMERAL)
+                    .build(),
+                Ok(stats) => CallbackDataBuilder::new()
+                    .embeds([stats_embed(title, &stats, locale)])
+                    .flags(MessageFlags::EPHEMERAL)
+                    .build(),
+                Err(_) => CallbackDataBuilder::new()
+                    .content(String::from("Failed to fetch the stats."))
+                    .flags(MessageFlags::EPHEMERAL)
+                    .build(),
+            }
+        };
+
+        context
+            .http
+            .interaction_callback(
+                context.interaction_id,
+                &context.interaction_token,
+                &InteractionResponse::ChannelMessageWithSource(reply),
+            )
+            .exec()
+            .await?;
+
+        Ok(())
+    }
+}
+
+fn stats_embed(title: String, stats: &QuoteStats, locale: Locale) -> Embed {
+    let field = |name: &str, value: String, inline: bool| EmbedField {
+        inline,
+        name: name.to_string(),
+        value,
+    };
+
+    let triggers: Vec<String> = stats
+        .triggers
+        .iter()
+        .map(|(trigger, count)| format!("{}: {}", trigger, count))
+        .collect();
+
+    let top_champions: Vec<String> = stats
+        .champions
+        .iter()
+        .enumerate()
+        .map(|(idx, (champion, count))| {
+            format!(
+                "{}. {} ({})",
+                idx + 1,
+                champions::display_name(champion, locale),
+                count
+            )
+        })
+        .collect();
+
+    let top_quotes: Vec<String> = stats
+        .quotes
+        .iter()
+        .map(|(champion, quote, count)| {
+            let mut quote = quote.clone();
+
+            if quote.chars().count() > 100 {
+                quote = quote.chars().take(99).chain(['…']).collect();
+            }
+
+            format!(
+                "**{}**: {} (×{})",
+                champions::display_name(champion, locale),
+                quote,
+                count
+            )
+        })
+        .collect();
+
+    // Oldest day first, so the chart reads left to right.
+    let max = stats.daily.iter().copied().max().unwrap_or_default().max(1);
+    let activity: String = stats
+        .daily
+        .iter()
+        .rev()
+        .map(|count| SPARKS[(*count * (SPARKS.len() as i64 - 1) / max) as usize])
+        .collect();
+
+    Embed {
+        author: None,
+        color: None,
+        description: None,
+        fields: vec![
+            field("Total quotes", stats.total.to_string(), true),
+            field(
+                "Last 30 days",
+                stats.daily.iter().sum::<i64>().to_string(),
+                true,
+            ),
+            field("By trigger", triggers.join("\n"), true),
+            field("Top champions", top_champions.join("\n"), false),
+            field("Most quoted lines", top_quotes.join("\n"), false),
+            field("Activity", format!("`{}`", activity), false),
+        ],
+        footer: Some(EmbedFooter {
+            icon_url: None,
+            proxy_icon_url: None,
+            text: String::from("Activity covers the last 30 days, oldest first"),
+        }),
+        image: None,
+        kind: String::from("rich"),
+        provider: None,
+        thumbnail: None,
+        timestamp: None,
+        title: Some(title),
+        url: None,
+        video: None,
+    }
+}
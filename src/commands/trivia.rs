@@ -0,0 +1,195 @@
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_model::{
+    application::callback::{Autocomplete, CallbackData, InteractionResponse},
+    channel::message::MessageFlags,
+};
+use twilight_util::builder::CallbackDataBuilder;
+
+use crate::{
+    champions,
+    trivia::{Difficulty, Guess, Start, TIME_LIMIT},
+    Error,
+};
+
+use super::{champion_choices, Context};
+
+#[derive(CommandModel, CreateCommand, Debug, PartialEq, Eq)]
+#[command(name = "trivia", desc = "Guess which champion said a quote")]
+pub enum TriviaCommand {
+    #[command(name = "start")]
+    Start(TriviaStartCommand),
+    #[command(name = "guess")]
+    Guess(TriviaGuessCommand),
+    #[command(name = "leaderboard")]
+    Leaderboard(TriviaLeaderboardCommand),
+}
+
+#[derive(CommandModel, CreateCommand, Debug, PartialEq, Eq)]
+#[command(name = "start", desc = "Start a round of trivia in this channel")]
+pub struct TriviaStartCommand {
+    #[command(desc = "How well known the quote is, defaults to normal")]
+    pub difficulty: Option<Difficulty>,
+}
+
+#[derive(CommandModel, CreateCommand, Debug, PartialEq, Eq)]
+#[command(name = "guess", desc = "Guess the champion of the running round")]
+pub struct TriviaGuessCommand {
+    #[command(autocomplete = true, desc = "The champion you think said it")]
+    pub champion: String,
+}
+
+#[derive(CommandModel, CreateCommand, Debug, PartialEq, Eq)]
+#[command(
+    name = "leaderboard",
+    desc = "View who has the most trivia points in this server"
+)]
+pub struct TriviaLeaderboardCommand {}
+
+impl TriviaCommand {
+    pub async fn run(&self, context: Context) -> Result<(), Error> {
+        let reply = match (self, context.guild_id) {
+            (Self::Start(command), Some(_)) => command.run(&context).await,
+            (Self::Guess(command), Some(guild_id)) => command.run(&context, guild_id).await,
+            (Self::Leaderboard(_), Some(guild_id)) => leaderboard(&context, guild_id).await,
+            (_, None) => ephemeral(String::from("This command can only be used in servers.")),
+        };
+
+        context
+            .http
+            .interaction_callback(
+                context.interaction_id,
+                &context.interaction_token,
+                &InteractionResponse::ChannelMessageWithSource(reply),
+            )
+            .exec()
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn autocomplete(&self, context: Context) -> Result<(), Error> {
+        let matches = match self {
+            Self::Guess(command) => champion_choices(&command.champion),
+            _ => Vec::new(),
+        };
+
+        context
+            .http
+            .interaction_callback(
+                context.interaction_id,
+                &context.interaction_token,
+                &InteractionResponse::Autocomplete(Autocomplete { choices: matches }),
+            )
+            .exec()
+            .await?;
+
+        Ok(())
+    }
+}
+
+impl TriviaStartCommand {
+    async fn run(&self, context: &Context) -> CallbackData {
+        let difficulty = self.difficulty.unwrap_or(Difficulty::Normal);
+
+        let locale = context
+            .database
+            .get_locale(context.user_id, context.guild_id)
+            .await
+            .unwrap_or_default();
+
+        let game = match context.trivia.start(context.channel_id, difficulty, locale) {
+            Start::Started(game) => game,
+            Start::AlreadyRunning => {
+                return ephemeral(String::from(
+                    "There's already a round running in this channel.",
+                ))
+            }
+            Start::NoQuote => {
+                return ephemeral(format!(
+                    "There are no {} quotes yet, try another difficulty.",
+                    difficulty.name().to_lowercase()
+                ))
+            }
+        };
+
+        let games = context.trivia.clone();
+        let channel_id = context.channel_id;
+        let token = context.interaction_token.clone();
+
+        tokio::spawn(async move {
+            let _ = games.expire(channel_id, game.id, token).await;
+        });
+
+        CallbackDataBuilder::new()
+            .content(format!(
+                "**Which champion said this?** ({}, {} seconds)\n> {}\nAnswer with /trivia guess.",
+                difficulty.name(),
+                TIME_LIMIT.as_secs(),
+                game.quote.text
+            ))
+            .build()
+    }
+}
+
+impl TriviaGuessCommand {
+    async fn run(&self, context: &Context, guild_id: i64) -> CallbackData {
+        let champion = match champions::resolve(&self.champion) {
+            Some(champion) => champion,
+            None => return ephemeral(String::from("Invalid champion.")),
+        };
+
+        match context.trivia.guess(context.channel_id, champion) {
+            Guess::Correct(game) => {
+                let points = game.difficulty.points();
+                let name = champions::display_name(game.champion, game.locale);
+
+                if context
+                    .database
+                    .add_trivia_points(guild_id, context.user_id, points)
+                    .await
+                    .is_err()
+                {
+                    return ephemeral(format!(
+                        "It was **{}**, but your points couldn't be saved.",
+                        name
+                    ));
+                }
+
+                CallbackDataBuilder::new()
+                    .content(format!(
+                        "<@{}> got it! It was **{}**. (+{} points)",
+                        context.user_id, name, points
+                    ))
+                    .build()
+            }
+            Guess::Wrong => ephemeral(String::from("Not quite, keep guessing.")),
+            Guess::NoGame => ephemeral(String::from(
+                "There's no round running in this channel, start one with /trivia start.",
+            )),
+        }
+    }
+}
+
+async fn leaderboard(context: &Context, guild_id: i64) -> CallbackData {
+    let text = match context.database.get_trivia_leaders(guild_id, 10).await {
+        Ok(leaders) if leaders.is_empty() => String::from("Nobody has scored here yet."),
+        Ok(leaders) => leaders
+            .into_iter()
+            .enumerate()
+            .map(|(idx, (user_id, points))| {
+                format!("{}. <@{}>: {} points", idx + 1, user_id, points)
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Err(_) => String::from("Failed to fetch the leaderboard."),
+    };
+
+    ephemeral(text)
+}
+
+fn ephemeral(text: String) -> CallbackData {
+    CallbackDataBuilder::new()
+        .content(text)
+        .flags(MessageFlags::EPHEMERAL)
+        .build()
+}
@@ -1,10 +1,13 @@
 use twilight_interactions::command::{CommandModel, CreateCommand};
-use twilight_model::{application::callback::InteractionResponse, channel::message::MessageFlags};
+use twilight_model::{
+    application::callback::InteractionResponse,
+    channel::{embed::EmbedField, message::MessageFlags},
+};
 use twilight_util::builder::CallbackDataBuilder;
 
-use crate::Error;
+use crate::{db::RateScope, Error};
 
-use super::Context;
+use super::{profile_embed, Context};
 
 #[derive(CommandModel, CreateCommand, Debug, PartialEq, Eq)]
 #[command(
@@ -15,19 +18,75 @@ pub struct WhoamiCommand {}
 
 impl WhoamiCommand {
     pub async fn run(&self, context: Context) -> Result<(), Error> {
-        let text = match context
+        let locale = context
             .database
-            .get_champion_and_rate(context.user_id)
+            .get_locale(context.user_id, context.guild_id)
             .await
-        {
-            Ok((champion, _)) => champion,
-            Err(_) => String::from("Failed to fetch your champion."),
-        };
+            .unwrap_or_default();
+
+        let reply = match context.database.get_user(context.user_id).await {
+            Ok(Some(settings)) => {
+                let rate = context
+                    .database
+                    .get_effective_rate(
+                        context.user_id,
+                        &settings,
+                        context.guild_id,
+                        context.channel_id.get() as i64,
+                    )
+                    .await
+                    .unwrap_or(settings.rate);
+
+                let quote_count = context
+                    .database
+                    .get_quote_count(context.user_id)
+                    .await
+                    .unwrap_or_default();
+
+                let mut embed =
+                    profile_embed(&context.display_name, &settings, locale, rate, quote_count);
 
-        let reply = CallbackDataBuilder::new()
-            .content(text)
-            .flags(MessageFlags::EPHEMERAL)
-            .build();
+                let overrides = context
+                    .database
+                    .get_rate_overrides(context.user_id)
+                    .await
+                    .unwrap_or_default();
+
+                if !overrides.is_empty() {
+                    let lines: Vec<String> = overrides
+                        .into_iter()
+                        .map(|(scope, scope_id, rate)| match scope {
+                            RateScope::Channel => format!("<#{}>: {}%", scope_id, rate),
+                            RateScope::Guild if Some(scope_id) == context.guild_id => {
+                                format!("This server: {}%", rate)
+                            }
+                            RateScope::Guild => format!("Server {}: {}%", scope_id, rate),
+                        })
+                        .collect();
+
+                    embed.fields.push(EmbedField {
+                        inline: false,
+                        name: String::from("Rate overrides"),
+                        value: lines.join("\n"),
+                    });
+                }
+
+                CallbackDataBuilder::new()
+                    .embeds([embed])
+                    .flags(MessageFlags::EPHEMERAL)
+                    .build()
+            }
+            Ok(None) => CallbackDataBuilder::new()
+                .content(String::from(
+                    "You haven't picked a champion yet, use /optin to get one.",
+                ))
+                .flags(MessageFlags::EPHEMERAL)
+                .build(),
+            Err(_) => CallbackDataBuilder::new()
+                .content(String::from("Failed to fetch your champion."))
+                .flags(MessageFlags::EPHEMERAL)
+                .build(),
+        };
 
         context
             .http
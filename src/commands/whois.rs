@@ -6,7 +6,7 @@ use twilight_util::builder::CallbackDataBuilder;
 
 use crate::Error;
 
-use super::Context;
+use super::{profile_embed, Context};
 
 #[derive(CommandModel, CreateCommand, Debug, PartialEq, Eq)]
 #[command(
@@ -20,19 +20,53 @@ pub struct WhoisCommand {
 
 impl WhoisCommand {
     pub async fn run(&self, context: Context) -> Result<(), Error> {
-        let text = match context
+        let locale = context
             .database
-            .get_champion_and_rate(self.user.id.get() as i64)
+            .get_locale(context.user_id, context.guild_id)
             .await
-        {
-            Ok((champion, _)) => champion,
-            Err(_) => String::from("Failed to fetch their champion."),
-        };
+            .unwrap_or_default();
+
+        let user_id = self.user.id.get() as i64;
+
+        let reply = match context.database.get_user(user_id).await {
+            Ok(Some(settings)) => {
+                let rate = context
+                    .database
+                    .get_effective_rate(
+                        user_id,
+                        &settings,
+                        context.guild_id,
+                        context.channel_id.get() as i64,
+                    )
+                    .await
+                    .unwrap_or(settings.rate);
 
-        let reply = CallbackDataBuilder::new()
-            .content(text)
-            .flags(MessageFlags::EPHEMERAL)
-            .build();
+                let quote_count = context
+                    .database
+                    .get_quote_count(user_id)
+                    .await
+                    .unwrap_or_default();
+
+                CallbackDataBuilder::new()
+                    .embeds([profile_embed(
+                        &self.user.name,
+                        &settings,
+                        locale,
+                        rate,
+                        quote_count,
+                    )])
+                    .flags(MessageFlags::EPHEMERAL)
+                    .build()
+            }
+            Ok(None) => CallbackDataBuilder::new()
+                .content(String::from("They haven't picked a champion yet."))
+                .flags(MessageFlags::EPHEMERAL)
+                .build(),
+            Err(_) => CallbackDataBuilder::new()
+                .content(String::from("Failed to fetch their champion."))
+                .flags(MessageFlags::EPHEMERAL)
+                .build(),
+        };
 
         context
             .http
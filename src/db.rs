@@ -1,14 +1,124 @@
-use dashmap::DashMap;
+use dashmap::{DashMap, DashSet};
 use rand::{prelude::IteratorRandom, thread_rng};
+use serde_json::{json, Value};
 use sqlx::{migrate::Migrator, SqlitePool};
 
-use std::path::Path;
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+};
 
-use crate::champions::CHAMPIONS;
+use crate::{
+    champions::{Voice, CHAMPIONS},
+    ddragon::{Locale, QuoteCategory},
+};
+
+#[derive(Clone, Debug)]
+pub struct UserSettings {
+    pub champion: String,
+    pub rate: i64,
+    /// Whether the user picked their rate, rather than keeping the default.
+    pub rate_set: bool,
+    pub audio: Option<bool>,
+    pub skin: Option<String>,
+    pub chaos: bool,
+    pub chaos_tags: Vec<String>,
+    pub locale: Option<Locale>,
+    pub opted_in: Option<bool>,
+    pub picked_at: Option<i64>,
+}
+
+#[derive(Clone, Debug)]
+pub struct GuildSettings {
+    pub audio: bool,
+    pub locale: Option<Locale>,
+    pub auto_enroll: bool,
+    pub enabled: bool,
+    pub default_rate: Option<i64>,
+    /// Channels and categories nobody is quoted in.
+    pub ignored: HashSet<i64>,
+}
+
+impl Default for GuildSettings {
+    fn default() -> Self {
+        Self {
+            audio: false,
+            locale: None,
+            auto_enroll: false,
+            enabled: true,
+            default_rate: None,
+            ignored: HashSet::new(),
+        }
+    }
+}
+
+/// Where a quote rate override applies, a server or a single channel.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RateScope {
+    Guild,
+    Channel,
+}
+
+impl RateScope {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Guild => "guild",
+            Self::Channel => "channel",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        [Self::Guild, Self::Channel]
+            .into_iter()
+            .find(|scope| scope.as_str() == name)
+    }
+}
+
+/// What made the bot post a quote.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum QuoteTrigger {
+    Random,
+    Mention,
+    Command,
+}
+
+impl QuoteTrigger {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Random => "random",
+            Self::Mention => "mention",
+            Self::Command => "command",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct QuoteStats {
+    pub total: i64,
+    pub triggers: Vec<(String, i64)>,
+    pub champions: Vec<(String, i64)>,
+    pub quotes: Vec<(String, String, i64)>,
+    /// Quotes per day over the last 30 days, today first.
+    pub daily: Vec<i64>,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DuelRecord {
+    pub wins: i64,
+    pub losses: i64,
+    pub draws: i64,
+}
 
 pub struct Database {
     pool: SqlitePool,
-    cache: DashMap<i64, (String, i64)>,
+    // Only people with settings are cached, everyone else who talks would
+    // otherwise pile up in here.
+    cache: DashMap<i64, UserSettings>,
+    rate_cache: DashMap<i64, Vec<(RateScope, i64, i64)>>,
+    guild_cache: DashMap<i64, GuildSettings>,
+    weight_cache: DashMap<i64, HashMap<QuoteCategory, i64>>,
+    // (guild ID, user ID) pairs already stored in guild_members.
+    member_cache: DashSet<(i64, i64)>,
 }
 
 impl Database {
@@ -16,6 +126,10 @@ impl Database {
         Ok(Self {
             pool: SqlitePool::connect(url).await?,
             cache: DashMap::new(),
+            rate_cache: DashMap::new(),
+            guild_cache: DashMap::new(),
+            weight_cache: DashMap::new(),
+            member_cache: DashSet::new(),
         })
     }
 
@@ -27,43 +141,517 @@ impl Database {
         Ok(())
     }
 
-    pub async fn get_champion_and_rate(&self, user_id: i64) -> Result<(String, i64), sqlx::Error> {
+    pub async fn get_user(&self, user_id: i64) -> Result<Option<UserSettings>, sqlx::Error> {
         if let Some(entry) = self.cache.get(&user_id) {
-            return Ok(entry.clone());
+            return Ok(Some(entry.clone()));
+        }
+
+        let row = sqlx::query!(
+            "SELECT champion, rate, rate_set, audio, skin, chaos, chaos_tags, locale, opted_in, picked_at FROM users WHERE id=?;",
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        let settings = row.map(|row| UserSettings {
+            champion: row.champion,
+            rate: row.rate,
+            rate_set: row.rate_set,
+            audio: row.audio,
+            skin: row.skin,
+            chaos: row.chaos,
+            chaos_tags: row
+                .chaos_tags
+                .map(|tags| tags.split(',').map(String::from).collect())
+                .unwrap_or_default(),
+            locale: row.locale.as_deref().and_then(Locale::from_code),
+            opted_in: row.opted_in,
+            picked_at: row.picked_at,
+        });
+
+        if let Some(settings) = &settings {
+            self.cache.insert(user_id, settings.clone());
         }
 
-        let row = sqlx::query!("SELECT champion, rate FROM users WHERE id=?;", user_id)
-            .fetch_optional(&self.pool)
+        Ok(settings)
+    }
+
+    /// Gives someone without settings a random champion, for servers that
+    /// quote everyone who hasn't opted out. People who used /forgetme are
+    /// never enrolled this way.
+    pub async fn enroll(&self, user_id: i64) -> Result<Option<UserSettings>, sqlx::Error> {
+        let champion = {
+            let mut rng = thread_rng();
+            CHAMPIONS.keys().choose(&mut rng).unwrap()
+        };
+
+        sqlx::query!(
+            "INSERT INTO users (id, champion, rate, picked_at) SELECT ?, ?, ?, strftime('%s', 'now') WHERE NOT EXISTS (SELECT 1 FROM forgotten_users WHERE user_id=?) ON CONFLICT(id) DO NOTHING;",
+            user_id,
+            champion,
+            10,
+            user_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        self.cache.remove(&user_id);
+
+        self.get_user(user_id).await
+    }
+
+    /// Everything stored about a user, for /mydata.
+    pub async fn export_user(&self, user_id: i64) -> Result<Value, sqlx::Error> {
+        let user = sqlx::query!(
+            "SELECT champion, rate, rate_set, audio, skin, chaos, chaos_tags, locale, opted_in, picked_at FROM users WHERE id=?;",
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        let deck = self.get_deck(user_id).await?;
+
+        let rate_overrides = self.get_rate_overrides(user_id).await?;
+
+        let trivia_scores = sqlx::query!(
+            "SELECT guild_id, points FROM trivia_scores WHERE user_id=?;",
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let duel_record = self.get_duel_record(user_id).await?;
+
+        let guilds = sqlx::query!(
+            "SELECT guild_id FROM guild_members WHERE user_id=?;",
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let quote_log = sqlx::query!(
+            "SELECT guild_id, channel_id, champion, voice, quote, trigger_type, timestamp FROM quote_log WHERE user_id=? ORDER BY id;",
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let forgotten = sqlx::query!(
+            "SELECT user_id FROM forgotten_users WHERE user_id=?;",
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(json!({
+            "user_id": user_id.to_string(),
+            "settings": user.map(|row| json!({
+                "champion": row.champion,
+                "rate": row.rate,
+                "rate_set": row.rate_set,
+                "audio": row.audio,
+                "skin": row.skin,
+                "chaos": row.chaos,
+                "chaos_tags": row.chaos_tags,
+                "locale": row.locale,
+                "opted_in": row.opted_in,
+                "picked_at": row.picked_at,
+            })),
+            "quote_deck": deck.map(|(champion, seed, position)| json!({
+                "champion": champion,
+                "seed": seed,
+                "position": position,
+            })),
+            "rate_overrides": rate_overrides
+                .into_iter()
+                .map(|(scope, scope_id, rate)| json!({
+                    "scope": scope.as_str(),
+                    "scope_id": scope_id.to_string(),
+                    "rate": rate,
+                }))
+                .collect::<Vec<_>>(),
+            "quote_log": quote_log
+                .into_iter()
+                .map(|row| json!({
+                    "guild_id": row.guild_id.map(|id| id.to_string()),
+                    "channel_id": row.channel_id.to_string(),
+                    "champion": row.champion,
+                    "voice": row.voice,
+                    "quote": row.quote,
+                    "trigger": row.trigger_type,
+                    "timestamp": row.timestamp,
+                }))
+                .collect::<Vec<_>>(),
+            "trivia_scores": trivia_scores
+                .into_iter()
+                .map(|row| json!({
+                    "guild_id": row.guild_id.to_string(),
+                    "points": row.points,
+                }))
+                .collect::<Vec<_>>(),
+            "duel_record": json!({
+                "wins": duel_record.wins,
+                "losses": duel_record.losses,
+                "draws": duel_record.draws,
+            }),
+            "guilds": guilds
+                .into_iter()
+                .map(|row| row.guild_id.to_string())
+                .collect::<Vec<_>>(),
+            "forgotten": forgotten.is_some(),
+        }))
+    }
+
+    /// Deletes everything stored about a user and keeps them from being
+    /// enrolled again until they opt in.
+    pub async fn forget_user(&self, user_id: i64) -> Result<(), sqlx::Error> {
+        let mut transaction = self.pool.begin().await?;
+
+        sqlx::query!("DELETE FROM users WHERE id=?;", user_id)
+            .execute(&mut transaction)
             .await?;
 
-        if let Some(row) = row {
-            Ok((row.champion, row.rate))
-        } else {
-            let champion = {
-                let mut rng = thread_rng();
-                CHAMPIONS.keys().choose(&mut rng).unwrap()
+        sqlx::query!("DELETE FROM quote_decks WHERE user_id=?;", user_id)
+            .execute(&mut transaction)
+            .await?;
+
+        sqlx::query!("DELETE FROM rate_overrides WHERE user_id=?;", user_id)
+            .execute(&mut transaction)
+            .await?;
+
+        sqlx::query!("DELETE FROM quote_log WHERE user_id=?;", user_id)
+            .execute(&mut transaction)
+            .await?;
+
+        sqlx::query!("DELETE FROM guild_members WHERE user_id=?;", user_id)
+            .execute(&mut transaction)
+            .await?;
+
+        sqlx::query!("DELETE FROM trivia_scores WHERE user_id=?;", user_id)
+            .execute(&mut transaction)
+            .await?;
+
+        sqlx::query!("DELETE FROM duel_records WHERE user_id=?;", user_id)
+            .execute(&mut transaction)
+            .await?;
+
+        sqlx::query!(
+            "INSERT INTO forgotten_users (user_id) VALUES (?) ON CONFLICT(user_id) DO NOTHING;",
+            user_id
+        )
+        .execute(&mut transaction)
+        .await?;
+
+        transaction.commit().await?;
+
+        self.cache.remove(&user_id);
+        self.rate_cache.remove(&user_id);
+        self.member_cache
+            .retain(|(_, member_id)| *member_id != user_id);
+
+        Ok(())
+    }
+
+    pub async fn log_quote(
+        &self,
+        user_id: i64,
+        guild_id: Option<i64>,
+        channel_id: i64,
+        voice: &Voice,
+        quote: &str,
+        trigger: QuoteTrigger,
+    ) -> Result<(), sqlx::Error> {
+        let trigger = trigger.as_str();
+
+        sqlx::query!(
+            "INSERT INTO quote_log (guild_id, channel_id, user_id, champion, voice, quote, trigger_type, timestamp) VALUES (?, ?, ?, ?, ?, ?, ?, strftime('%s', 'now'));",
+            guild_id,
+            channel_id,
+            user_id,
+            voice.champion,
+            voice.key,
+            quote,
+            trigger
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// How many times someone has been quoted anywhere.
+    pub async fn get_quote_count(&self, user_id: i64) -> Result<i64, sqlx::Error> {
+        let row = sqlx::query!(
+            r#"SELECT COUNT(*) AS "count!: i64" FROM quote_log WHERE user_id=?;"#,
+            user_id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(row.count)
+    }
+
+    /// Quote statistics for a user, a server, or a user within a server.
+    pub async fn get_quote_stats(
+        &self,
+        user_id: Option<i64>,
+        guild_id: Option<i64>,
+    ) -> Result<QuoteStats, sqlx::Error> {
+        let triggers = sqlx::query!(
+            r#"SELECT trigger_type, COUNT(*) AS "count!: i64" FROM quote_log WHERE (?1 IS NULL OR user_id=?1) AND (?2 IS NULL OR guild_id=?2) GROUP BY trigger_type ORDER BY 2 DESC;"#,
+            user_id,
+            guild_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let champions = sqlx::query!(
+            r#"SELECT champion, COUNT(*) AS "count!: i64" FROM quote_log WHERE (?1 IS NULL OR user_id=?1) AND (?2 IS NULL OR guild_id=?2) GROUP BY champion ORDER BY 2 DESC LIMIT 5;"#,
+            user_id,
+            guild_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let quotes = sqlx::query!(
+            r#"SELECT champion, quote, COUNT(*) AS "count!: i64" FROM quote_log WHERE (?1 IS NULL OR user_id=?1) AND (?2 IS NULL OR guild_id=?2) GROUP BY champion, quote ORDER BY 3 DESC LIMIT 5;"#,
+            user_id,
+            guild_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let days = sqlx::query!(
+            r#"SELECT (strftime('%s', 'now') - timestamp) / 86400 AS "days_ago!: i64", COUNT(*) AS "count!: i64" FROM quote_log WHERE (?1 IS NULL OR user_id=?1) AND (?2 IS NULL OR guild_id=?2) AND timestamp > strftime('%s', 'now') - 30 * 86400 GROUP BY 1;"#,
+            user_id,
+            guild_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut daily = vec![0; 30];
+
+        for row in days {
+            if let Some(count) = usize::try_from(row.days_ago)
+                .ok()
+                .and_then(|days_ago| daily.get_mut(days_ago))
+            {
+                *count = row.count;
+            }
+        }
+
+        Ok(QuoteStats {
+            total: triggers.iter().map(|row| row.count).sum(),
+            triggers: triggers
+                .into_iter()
+                .map(|row| (row.trigger_type, row.count))
+                .collect(),
+            champions: champions
+                .into_iter()
+                .map(|row| (row.champion, row.count))
+                .collect(),
+            quotes: quotes
+                .into_iter()
+                .map(|row| (row.champion, row.quote, row.count))
+                .collect(),
+            daily,
+        })
+    }
+
+    /// Remembers that a user with settings is part of a server, for its
+    /// leaderboards.
+    pub async fn add_guild_member(&self, guild_id: i64, user_id: i64) -> Result<(), sqlx::Error> {
+        if self.member_cache.contains(&(guild_id, user_id)) {
+            return Ok(());
+        }
+
+        sqlx::query!(
+            "INSERT INTO guild_members (guild_id, user_id) VALUES (?, ?) ON CONFLICT(guild_id, user_id) DO NOTHING;",
+            guild_id,
+            user_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        self.member_cache.insert((guild_id, user_id));
+
+        Ok(())
+    }
+
+    /// The users quoted most in a server and how often, leaving out the
+    /// quotes they asked for themselves.
+    pub async fn get_quote_leaders(
+        &self,
+        guild_id: i64,
+        limit: i64,
+    ) -> Result<Vec<(i64, i64)>, sqlx::Error> {
+        let command = QuoteTrigger::Command.as_str();
+
+        let rows = sqlx::query!(
+            r#"SELECT user_id, COUNT(*) AS "count!: i64" FROM quote_log WHERE guild_id=? AND trigger_type!=? GROUP BY user_id ORDER BY 2 DESC LIMIT ?;"#,
+            guild_id,
+            command,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.user_id, row.count))
+            .collect())
+    }
+
+    /// How many of a server's members picked each champion.
+    pub async fn get_champion_picks(
+        &self,
+        guild_id: i64,
+    ) -> Result<HashMap<String, i64>, sqlx::Error> {
+        let rows = sqlx::query!(
+            r#"SELECT users.champion, COUNT(*) AS "count!: i64" FROM users INNER JOIN guild_members ON guild_members.user_id=users.id WHERE guild_members.guild_id=? GROUP BY users.champion;"#,
+            guild_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.champion, row.count))
+            .collect())
+    }
+
+    pub async fn add_trivia_points(
+        &self,
+        guild_id: i64,
+        user_id: i64,
+        points: i64,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "INSERT INTO trivia_scores (guild_id, user_id, points) VALUES (?, ?, ?) ON CONFLICT(guild_id, user_id) DO UPDATE SET points=points+?;",
+            guild_id,
+            user_id,
+            points,
+            points
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// The users with the most trivia points in a server.
+    pub async fn get_trivia_leaders(
+        &self,
+        guild_id: i64,
+        limit: i64,
+    ) -> Result<Vec<(i64, i64)>, sqlx::Error> {
+        let rows = sqlx::query!(
+            "SELECT user_id, points FROM trivia_scores WHERE guild_id=? ORDER BY points DESC LIMIT ?;",
+            guild_id,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.user_id, row.points))
+            .collect())
+    }
+
+    /// Records a duel between two users, `winner` being 0 or 1 for the
+    /// first or second user and `None` for a draw.
+    pub async fn record_duel(
+        &self,
+        user_ids: [i64; 2],
+        winner: Option<usize>,
+    ) -> Result<(), sqlx::Error> {
+        let mut transaction = self.pool.begin().await?;
+
+        for (idx, user_id) in user_ids.into_iter().enumerate() {
+            let (wins, losses, draws) = match winner {
+                Some(winner) if winner == idx => (1, 0, 0),
+                Some(_) => (0, 1, 0),
+                None => (0, 0, 1),
             };
 
             sqlx::query!(
-                "INSERT INTO users (id, champion, rate) VALUES (?, ?, ?);",
+                "INSERT INTO duel_records (user_id, wins, losses, draws) VALUES (?, ?, ?, ?) ON CONFLICT(user_id) DO UPDATE SET wins=wins+?, losses=losses+?, draws=draws+?;",
                 user_id,
-                champion,
-                10
+                wins,
+                losses,
+                draws,
+                wins,
+                losses,
+                draws
             )
-            .execute(&self.pool)
+            .execute(&mut transaction)
             .await?;
+        }
+
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    pub async fn get_duel_record(&self, user_id: i64) -> Result<DuelRecord, sqlx::Error> {
+        let row = sqlx::query!(
+            "SELECT wins, losses, draws FROM duel_records WHERE user_id=?;",
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map_or_else(DuelRecord::default, |row| DuelRecord {
+            wins: row.wins,
+            losses: row.losses,
+            draws: row.draws,
+        }))
+    }
+
+    pub async fn set_opted_in(&self, user_id: i64, opted_in: bool) -> Result<(), sqlx::Error> {
+        let champion = {
+            let mut rng = thread_rng();
+            CHAMPIONS.keys().choose(&mut rng).unwrap()
+        };
 
-            Ok((champion.clone(), 10))
+        if opted_in {
+            sqlx::query!("DELETE FROM forgotten_users WHERE user_id=?;", user_id)
+                .execute(&self.pool)
+                .await?;
         }
+
+        sqlx::query!(
+            "INSERT INTO users (id, champion, rate, picked_at, opted_in) VALUES (?, ?, ?, strftime('%s', 'now'), ?) ON CONFLICT(id) DO UPDATE SET opted_in=?;",
+            user_id,
+            champion,
+            10,
+            opted_in,
+            opted_in
+        )
+        .execute(&self.pool)
+        .await?;
+
+        self.cache.remove(&user_id);
+
+        Ok(())
     }
 
-    pub async fn set_champion(&self, user_id: i64, champion: &str) -> Result<(), sqlx::Error> {
+    /// Picking a champion also opts in to being quoted.
+    pub async fn set_champion(
+        &self,
+        user_id: i64,
+        champion: &str,
+        skin: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
         sqlx::query!(
-            "INSERT INTO users (id, champion, rate) VALUES (?, ?, ?) ON CONFLICT(id) DO UPDATE SET champion=?;",
+            "INSERT INTO users (id, champion, rate, picked_at, skin, opted_in) VALUES (?, ?, ?, strftime('%s', 'now'), ?, 1) ON CONFLICT(id) DO UPDATE SET picked_at=CASE WHEN champion=excluded.champion THEN picked_at ELSE strftime('%s', 'now') END, champion=?, skin=?, opted_in=1;",
             user_id,
             champion,
             10,
-            champion
+            skin,
+            champion,
+            skin
         )
         .execute(&self.pool)
         .await?;
@@ -79,18 +667,579 @@ impl Database {
             CHAMPIONS.keys().choose(&mut rng).unwrap()
         };
 
+        // Settings changed after /forgetme keep the user opted out, only /iam
+        // and /optin opt them back in. The same goes for the setters below.
         sqlx::query!(
-            "INSERT INTO users (id, champion, rate) VALUES (?, ?, ?) ON CONFLICT(id) DO UPDATE SET rate=?;",
+            "INSERT INTO users (id, champion, rate, rate_set, picked_at, opted_in) VALUES (?, ?, ?, 1, strftime('%s', 'now'), (SELECT 0 FROM forgotten_users WHERE user_id=?)) ON CONFLICT(id) DO UPDATE SET rate=?, rate_set=1;",
             user_id,
             champion,
             rate,
+            user_id,
+            rate
+        )
+        .execute(&self.pool)
+        .await?;
+
+        self.cache.remove(&user_id);
+
+        Ok(())
+    }
+
+    pub async fn get_rate_overrides(
+        &self,
+        user_id: i64,
+    ) -> Result<Vec<(RateScope, i64, i64)>, sqlx::Error> {
+        if let Some(entry) = self.rate_cache.get(&user_id) {
+            return Ok(entry.clone());
+        }
+
+        let rows = sqlx::query!(
+            "SELECT scope, scope_id, rate FROM rate_overrides WHERE user_id=?;",
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let overrides: Vec<(RateScope, i64, i64)> = rows
+            .into_iter()
+            .filter_map(|row| Some((RateScope::from_name(&row.scope)?, row.scope_id, row.rate)))
+            .collect();
+
+        self.rate_cache.insert(user_id, overrides.clone());
+
+        Ok(overrides)
+    }
+
+    /// The most specific rate override for a message, the channel's before the
+    /// server's. `None` means the user's global rate applies.
+    pub async fn get_rate_override(
+        &self,
+        user_id: i64,
+        guild_id: Option<i64>,
+        channel_id: i64,
+    ) -> Result<Option<i64>, sqlx::Error> {
+        let overrides = self.get_rate_overrides(user_id).await?;
+
+        let find = |scope: RateScope, scope_id: i64| {
+            overrides
+                .iter()
+                .find(|(other_scope, other_id, _)| *other_scope == scope && *other_id == scope_id)
+                .map(|(_, _, rate)| *rate)
+        };
+
+        Ok(find(RateScope::Channel, channel_id)
+            .or_else(|| guild_id.and_then(|guild_id| find(RateScope::Guild, guild_id))))
+    }
+
+    /// The rate someone is quoted at in a channel: their override for it,
+    /// else the server's default if they never picked a rate, else their own.
+    pub async fn get_effective_rate(
+        &self,
+        user_id: i64,
+        settings: &UserSettings,
+        guild_id: Option<i64>,
+        channel_id: i64,
+    ) -> Result<i64, sqlx::Error> {
+        if let Some(rate) = self
+            .get_rate_override(user_id, guild_id, channel_id)
+            .await?
+        {
+            return Ok(rate);
+        }
+
+        let default_rate = match guild_id {
+            Some(guild_id) => self.get_guild_settings(guild_id).await?.default_rate,
+            None => None,
+        };
+
+        Ok(default_rate
+            .filter(|_| !settings.rate_set)
+            .unwrap_or(settings.rate))
+    }
+
+    pub async fn set_rate_override(
+        &self,
+        user_id: i64,
+        scope: RateScope,
+        scope_id: i64,
+        rate: i64,
+    ) -> Result<(), sqlx::Error> {
+        let scope_name = scope.as_str();
+
+        sqlx::query!(
+            "INSERT INTO rate_overrides (user_id, scope, scope_id, rate) VALUES (?, ?, ?, ?) ON CONFLICT(user_id, scope, scope_id) DO UPDATE SET rate=?;",
+            user_id,
+            scope_name,
+            scope_id,
+            rate,
             rate
         )
         .execute(&self.pool)
         .await?;
 
+        self.rate_cache.remove(&user_id);
+
+        Ok(())
+    }
+
+    /// Returns `false` if there was no such override.
+    pub async fn remove_rate_override(
+        &self,
+        user_id: i64,
+        scope: RateScope,
+        scope_id: i64,
+    ) -> Result<bool, sqlx::Error> {
+        let scope_name = scope.as_str();
+
+        let result = sqlx::query!(
+            "DELETE FROM rate_overrides WHERE user_id=? AND scope=? AND scope_id=?;",
+            user_id,
+            scope_name,
+            scope_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        self.rate_cache.remove(&user_id);
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn set_audio(&self, user_id: i64, audio: Option<bool>) -> Result<(), sqlx::Error> {
+        let champion = {
+            let mut rng = thread_rng();
+            CHAMPIONS.keys().choose(&mut rng).unwrap()
+        };
+
+        sqlx::query!(
+            "INSERT INTO users (id, champion, rate, picked_at, audio, opted_in) VALUES (?, ?, ?, strftime('%s', 'now'), ?, (SELECT 0 FROM forgotten_users WHERE user_id=?)) ON CONFLICT(id) DO UPDATE SET audio=?;",
+            user_id,
+            champion,
+            10,
+            audio,
+            user_id,
+            audio
+        )
+        .execute(&self.pool)
+        .await?;
+
+        self.cache.remove(&user_id);
+
+        Ok(())
+    }
+
+    pub async fn set_chaos(
+        &self,
+        user_id: i64,
+        chaos: bool,
+        tags: &[&str],
+    ) -> Result<(), sqlx::Error> {
+        let champion = {
+            let mut rng = thread_rng();
+            CHAMPIONS.keys().choose(&mut rng).unwrap()
+        };
+
+        let tags = if tags.is_empty() {
+            None
+        } else {
+            Some(tags.join(","))
+        };
+
+        sqlx::query!(
+            "INSERT INTO users (id, champion, rate, picked_at, chaos, chaos_tags, opted_in) VALUES (?, ?, ?, strftime('%s', 'now'), ?, ?, (SELECT 0 FROM forgotten_users WHERE user_id=?)) ON CONFLICT(id) DO UPDATE SET chaos=?, chaos_tags=?;",
+            user_id,
+            champion,
+            10,
+            chaos,
+            tags,
+            user_id,
+            chaos,
+            tags
+        )
+        .execute(&self.pool)
+        .await?;
+
+        self.cache.remove(&user_id);
+
+        Ok(())
+    }
+
+    pub async fn set_locale(&self, user_id: i64, locale: Locale) -> Result<(), sqlx::Error> {
+        let champion = {
+            let mut rng = thread_rng();
+            CHAMPIONS.keys().choose(&mut rng).unwrap()
+        };
+
+        let locale = locale.code();
+
+        sqlx::query!(
+            "INSERT INTO users (id, champion, rate, picked_at, locale, opted_in) VALUES (?, ?, ?, strftime('%s', 'now'), ?, (SELECT 0 FROM forgotten_users WHERE user_id=?)) ON CONFLICT(id) DO UPDATE SET locale=?;",
+            user_id,
+            champion,
+            10,
+            locale,
+            user_id,
+            locale
+        )
+        .execute(&self.pool)
+        .await?;
+
         self.cache.remove(&user_id);
 
         Ok(())
     }
+
+    /// The language a user is quoted in: their own choice, then the server's,
+    /// then English.
+    pub async fn get_locale(
+        &self,
+        user_id: i64,
+        guild_id: Option<i64>,
+    ) -> Result<Locale, sqlx::Error> {
+        if let Some(locale) = self.get_user(user_id).await?.and_then(|user| user.locale) {
+            return Ok(locale);
+        }
+
+        let locale = match guild_id {
+            Some(guild_id) => self.get_guild_settings(guild_id).await?.locale,
+            None => None,
+        };
+
+        Ok(locale.unwrap_or_default())
+    }
+
+    pub async fn get_guild_settings(&self, guild_id: i64) -> Result<GuildSettings, sqlx::Error> {
+        if let Some(entry) = self.guild_cache.get(&guild_id) {
+            return Ok(entry.clone());
+        }
+
+        let row = sqlx::query!(
+            "SELECT audio, locale, auto_enroll, enabled, default_rate FROM guild_settings WHERE guild_id=?;",
+            guild_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        let ignored = sqlx::query!(
+            "SELECT channel_id FROM ignored_channels WHERE guild_id=?;",
+            guild_id
+        )
+        .fetch_all(&self.pool)
+        .await?
+        .into_iter()
+        .map(|row| row.channel_id)
+        .collect();
+
+        let settings = match row {
+            Some(row) => GuildSettings {
+                audio: row.audio,
+                locale: row.locale.as_deref().and_then(Locale::from_code),
+                auto_enroll: row.auto_enroll,
+                enabled: row.enabled,
+                default_rate: row.default_rate,
+                ignored,
+            },
+            None => GuildSettings {
+                ignored,
+                ..GuildSettings::default()
+            },
+        };
+
+        self.guild_cache.insert(guild_id, settings.clone());
+
+        Ok(settings)
+    }
+
+    pub async fn set_guild_audio(&self, guild_id: i64, audio: bool) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "INSERT INTO guild_settings (guild_id, audio) VALUES (?, ?) ON CONFLICT(guild_id) DO UPDATE SET audio=?;",
+            guild_id,
+            audio,
+            audio
+        )
+        .execute(&self.pool)
+        .await?;
+
+        self.guild_cache.remove(&guild_id);
+
+        Ok(())
+    }
+
+    pub async fn set_guild_locale(&self, guild_id: i64, locale: Locale) -> Result<(), sqlx::Error> {
+        let locale = locale.code();
+
+        sqlx::query!(
+            "INSERT INTO guild_settings (guild_id, locale) VALUES (?, ?) ON CONFLICT(guild_id) DO UPDATE SET locale=?;",
+            guild_id,
+            locale,
+            locale
+        )
+        .execute(&self.pool)
+        .await?;
+
+        self.guild_cache.remove(&guild_id);
+
+        Ok(())
+    }
+
+    pub async fn set_guild_auto_enroll(
+        &self,
+        guild_id: i64,
+        auto_enroll: bool,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "INSERT INTO guild_settings (guild_id, auto_enroll) VALUES (?, ?) ON CONFLICT(guild_id) DO UPDATE SET auto_enroll=?;",
+            guild_id,
+            auto_enroll,
+            auto_enroll
+        )
+        .execute(&self.pool)
+        .await?;
+
+        self.guild_cache.remove(&guild_id);
+
+        Ok(())
+    }
+
+    pub async fn set_guild_enabled(&self, guild_id: i64, enabled: bool) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "INSERT INTO guild_settings (guild_id, enabled) VALUES (?, ?) ON CONFLICT(guild_id) DO UPDATE SET enabled=?;",
+            guild_id,
+            enabled,
+            enabled
+        )
+        .execute(&self.pool)
+        .await?;
+
+        self.guild_cache.remove(&guild_id);
+
+        Ok(())
+    }
+
+    pub async fn set_guild_default_rate(
+        &self,
+        guild_id: i64,
+        default_rate: Option<i64>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "INSERT INTO guild_settings (guild_id, default_rate) VALUES (?, ?) ON CONFLICT(guild_id) DO UPDATE SET default_rate=?;",
+            guild_id,
+            default_rate,
+            default_rate
+        )
+        .execute(&self.pool)
+        .await?;
+
+        self.guild_cache.remove(&guild_id);
+
+        Ok(())
+    }
+
+    /// Ignores or unignores a channel or category. Returns whether anything
+    /// changed.
+    pub async fn set_channel_ignored(
+        &self,
+        guild_id: i64,
+        channel_id: i64,
+        ignored: bool,
+    ) -> Result<bool, sqlx::Error> {
+        let result = if ignored {
+            sqlx::query!(
+                "INSERT INTO ignored_channels (channel_id, guild_id) VALUES (?, ?) ON CONFLICT(channel_id) DO NOTHING;",
+                channel_id,
+                guild_id
+            )
+            .execute(&self.pool)
+            .await?
+        } else {
+            sqlx::query!(
+                "DELETE FROM ignored_channels WHERE channel_id=? AND guild_id=?;",
+                channel_id,
+                guild_id
+            )
+            .execute(&self.pool)
+            .await?
+        };
+
+        self.guild_cache.remove(&guild_id);
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Subscribes a channel, keeping the later of its stored and the given
+    /// last posted day so a resubscribe never posts the same day twice.
+    pub async fn subscribe_qotd(
+        &self,
+        channel_id: i64,
+        guild_id: i64,
+        minute: i64,
+        last_posted: Option<i64>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "INSERT INTO qotd_subscriptions (channel_id, guild_id, minute, last_posted, active) VALUES (?, ?, ?, ?, 1) ON CONFLICT(channel_id) DO UPDATE SET minute=excluded.minute, active=1, last_posted=MAX(COALESCE(qotd_subscriptions.last_posted, excluded.last_posted), COALESCE(excluded.last_posted, qotd_subscriptions.last_posted));",
+            channel_id,
+            guild_id,
+            minute,
+            last_posted
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn unsubscribe_qotd(&self, channel_id: i64) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "UPDATE qotd_subscriptions SET active=0 WHERE channel_id=? AND active=1;",
+            channel_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Channels whose post for `day` is due by `minute` and hasn't gone out.
+    pub async fn get_due_qotd(
+        &self,
+        day: i64,
+        minute: i64,
+    ) -> Result<Vec<(i64, i64)>, sqlx::Error> {
+        let rows = sqlx::query!(
+            "SELECT channel_id, guild_id FROM qotd_subscriptions WHERE active=1 AND minute <= ? AND (last_posted IS NULL OR last_posted < ?);",
+            minute,
+            day
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.channel_id, row.guild_id))
+            .collect())
+    }
+
+    /// Marks a channel's post for `day` as sent. Returns `false` if it already
+    /// was, so each day is only ever posted once.
+    pub async fn claim_qotd(&self, channel_id: i64, day: i64) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "UPDATE qotd_subscriptions SET last_posted=? WHERE channel_id=? AND (last_posted IS NULL OR last_posted < ?);",
+            day,
+            channel_id,
+            day
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn get_deck(&self, user_id: i64) -> Result<Option<(String, i64, i64)>, sqlx::Error> {
+        let row = sqlx::query!(
+            "SELECT champion, seed, position FROM quote_decks WHERE user_id=?;",
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|row| (row.champion, row.seed, row.position)))
+    }
+
+    /// Claims the next card of the user's deck for `champion` and `layout`
+    /// and returns the deck's seed with the claimed position. Returns `None`
+    /// if there is no such deck or all of its `len` cards were dealt.
+    pub async fn advance_deck(
+        &self,
+        user_id: i64,
+        champion: &str,
+        layout: i64,
+        len: i64,
+    ) -> Result<Option<(i64, i64)>, sqlx::Error> {
+        // Claiming in a single statement keeps concurrent draws from dealing
+        // the same card twice.
+        let row = sqlx::query!(
+            "UPDATE quote_decks SET position=position+1 WHERE user_id=? AND champion=? AND layout=? AND position<? RETURNING seed, position;",
+            user_id,
+            champion,
+            layout,
+            len
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|row| (row.seed, row.position - 1)))
+    }
+
+    /// Replaces the user's deck with a new one whose first card is dealt,
+    /// unless a concurrent draw already replaced it. Returns whether the
+    /// deck was replaced.
+    pub async fn reset_deck(
+        &self,
+        user_id: i64,
+        champion: &str,
+        layout: i64,
+        seed: i64,
+        len: i64,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "INSERT INTO quote_decks (user_id, champion, layout, seed, position) VALUES (?, ?, ?, ?, 1) ON CONFLICT(user_id) DO UPDATE SET champion=excluded.champion, layout=excluded.layout, seed=excluded.seed, position=1 WHERE quote_decks.champion<>excluded.champion OR quote_decks.layout<>excluded.layout OR quote_decks.position>=?;",
+            user_id,
+            champion,
+            layout,
+            seed,
+            len
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn get_category_weights(
+        &self,
+        guild_id: i64,
+    ) -> Result<HashMap<QuoteCategory, i64>, sqlx::Error> {
+        if let Some(entry) = self.weight_cache.get(&guild_id) {
+            return Ok(entry.clone());
+        }
+
+        let rows = sqlx::query!(
+            "SELECT category, weight FROM category_weights WHERE guild_id=?;",
+            guild_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let weights: HashMap<QuoteCategory, i64> = rows
+            .into_iter()
+            .filter_map(|row| Some((QuoteCategory::from_name(&row.category)?, row.weight)))
+            .collect();
+
+        self.weight_cache.insert(guild_id, weights.clone());
+
+        Ok(weights)
+    }
+
+    pub async fn set_category_weight(
+        &self,
+        guild_id: i64,
+        category: QuoteCategory,
+        weight: i64,
+    ) -> Result<(), sqlx::Error> {
+        let category = category.as_str();
+
+        sqlx::query!(
+            "INSERT INTO category_weights (guild_id, category, weight) VALUES (?, ?, ?) ON CONFLICT(guild_id, category) DO UPDATE SET weight=?;",
+            guild_id,
+            category,
+            weight,
+            weight
+        )
+        .execute(&self.pool)
+        .await?;
+
+        self.weight_cache.remove(&guild_id);
+
+        Ok(())
+    }
 }
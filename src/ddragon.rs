@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use twilight_interactions::command::{CommandOption, CreateOption};
 
 use std::collections::HashMap;
 
@@ -25,7 +26,7 @@ pub struct Champion {
     pub stats: ChampionStats,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct ChampionInfo {
     pub attack: u8,
     pub defense: u8,
@@ -44,7 +45,7 @@ pub struct ChampionImage {
     pub h: u32,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct ChampionStats {
     pub hp: f32,
     pub hpperlevel: f32,
@@ -68,15 +69,321 @@ pub struct ChampionStats {
     pub attackspeed: f32,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct ChampionDetailData {
+    pub data: HashMap<String, ChampionDetail>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ChampionDetail {
+    pub id: String,
+    pub skins: Vec<ChampionSkin>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ChampionSkin {
+    pub id: String,
+    pub num: u32,
+    pub name: String,
+    pub chromas: bool,
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct ChampionExport {
     pub name: String,
     pub id: String,
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub blurb: String,
+    #[serde(default)]
+    pub partype: String,
+    #[serde(default)]
+    pub info: Option<ChampionInfo>,
+    #[serde(default)]
+    pub stats: Option<ChampionStats>,
+    pub icon: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub skins: Vec<SkinExport>,
+    #[serde(default)]
+    pub names: HashMap<String, String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SkinExport {
+    pub name: String,
+    pub icon: String,
+    pub splash: String,
+}
+
+#[derive(
+    Clone,
+    Copy,
+    CommandOption,
+    CreateOption,
+    Debug,
+    Default,
+    Deserialize,
+    Eq,
+    Hash,
+    PartialEq,
+    Serialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum QuoteCategory {
+    #[option(name = "Pick", value = "pick")]
+    Pick,
+    #[option(name = "Ban", value = "ban")]
+    Ban,
+    #[option(name = "Movement", value = "movement")]
+    Movement,
+    #[option(name = "Attacking", value = "attack")]
+    Attack,
+    #[option(name = "Taunt", value = "taunt")]
+    Taunt,
+    #[option(name = "Joke", value = "joke")]
+    Joke,
+    #[option(name = "Laugh", value = "laugh")]
+    Laugh,
+    #[option(name = "Upon killing", value = "kill")]
+    Kill,
+    #[option(name = "Upon using an ability", value = "ability")]
+    Ability,
+    #[option(name = "Upon using Recall", value = "recall")]
+    Recall,
+    #[option(name = "Upon dying", value = "death")]
+    Death,
+    #[option(name = "Other", value = "other")]
+    #[default]
+    Other,
+}
+
+impl QuoteCategory {
+    pub const ALL: [Self; 12] = [
+        Self::Pick,
+        Self::Ban,
+        Self::Movement,
+        Self::Attack,
+        Self::Taunt,
+        Self::Joke,
+        Self::Laugh,
+        Self::Kill,
+        Self::Ability,
+        Self::Recall,
+        Self::Death,
+        Self::Other,
+    ];
+
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Pick => "pick",
+            Self::Ban => "ban",
+            Self::Movement => "movement",
+            Self::Attack => "attack",
+            Self::Taunt => "taunt",
+            Self::Joke => "joke",
+            Self::Laugh => "laugh",
+            Self::Kill => "kill",
+            Self::Ability => "ability",
+            Self::Recall => "recall",
+            Self::Death => "death",
+            Self::Other => "other",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|category| category.as_str() == name)
+    }
+}
+
+#[derive(Clone, Copy, CommandOption, CreateOption, Debug, Eq, Hash, PartialEq)]
+pub enum ChampionTag {
+    #[option(name = "Assassin", value = "Assassin")]
+    Assassin,
+    #[option(name = "Fighter", value = "Fighter")]
+    Fighter,
+    #[option(name = "Mage", value = "Mage")]
+    Mage,
+    #[option(name = "Marksman", value = "Marksman")]
+    Marksman,
+    #[option(name = "Support", value = "Support")]
+    Support,
+    #[option(name = "Tank", value = "Tank")]
+    Tank,
+}
+
+impl ChampionTag {
+    pub const ALL: [Self; 6] = [
+        Self::Assassin,
+        Self::Fighter,
+        Self::Mage,
+        Self::Marksman,
+        Self::Support,
+        Self::Tank,
+    ];
+
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Assassin => "Assassin",
+            Self::Fighter => "Fighter",
+            Self::Mage => "Mage",
+            Self::Marksman => "Marksman",
+            Self::Support => "Support",
+            Self::Tank => "Tank",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|tag| tag.as_str() == name)
+    }
+}
+
+#[derive(Clone, Copy, CommandOption, CreateOption, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Locale {
+    #[option(name = "English", value = "en_US")]
+    #[default]
+    English,
+    #[option(name = "Deutsch", value = "de_DE")]
+    German,
+}
+
+impl Locale {
+    pub const ALL: [Self; 2] = [Self::English, Self::German];
+
+    pub const fn code(self) -> &'static str {
+        match self {
+            Self::English => "en_US",
+            Self::German => "de_DE",
+        }
+    }
+
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::English => "English",
+            Self::German => "Deutsch",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|locale| locale.code() == code)
+    }
+
+    /// The fandom wiki in this language, where its voice lines come from.
+    pub const fn wiki_url(self) -> &'static str {
+        match self {
+            Self::English => "https://leagueoflegends.fandom.com/wiki/",
+            Self::German => "https://leagueoflegends.fandom.com/de/wiki/",
+        }
+    }
+
+    /// The subpage of a champion's wiki article that lists their voice lines.
+    pub const fn audio_page(self) -> &'static str {
+        match self {
+            Self::English => "LoL/Audio",
+            Self::German => "Zitate",
+        }
+    }
+}
+
+impl ChampionStats {
+    /// How much of a stat's per-level growth applies at `level`, following
+    /// the game's growth curve.
+    fn growth(level: u8) -> f32 {
+        let levels = f32::from(level.saturating_sub(1));
+
+        levels * (0.7025 + 0.0175 * levels)
+    }
+
+    pub fn at_level(base: f32, per_level: f32, level: u8) -> f32 {
+        base + per_level * Self::growth(level)
+    }
+
+    /// Attack speed grows by a percentage of the base value instead.
+    pub fn attack_speed_at_level(&self, level: u8) -> f32 {
+        self.attackspeed * (1.0 + self.attackspeedperlevel / 100.0 * Self::growth(level))
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(from = "QuoteRepr")]
+pub struct Quote {
+    pub text: String,
+    pub category: QuoteCategory,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audio: Option<String>,
+}
+
+// Older exports stored every quote as a bare string.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum QuoteRepr {
+    Text(String),
+    Full {
+        text: String,
+        #[serde(default)]
+        category: QuoteCategory,
+        #[serde(default)]
+        target: Option<String>,
+        #[serde(default)]
+        audio: Option<String>,
+    },
+}
+
+impl From<QuoteRepr> for Quote {
+    fn from(repr: QuoteRepr) -> Self {
+        match repr {
+            QuoteRepr::Text(text) => Self {
+                text,
+                category: QuoteCategory::default(),
+                target: None,
+                audio: None,
+            },
+            QuoteRepr::Full {
+                text,
+                category,
+                target,
+                audio,
+            } => Self {
+                text,
+                category,
+                target,
+                audio,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SkinQuoteExport {
+    pub quotes: Vec<Quote>,
     pub icon: String,
+    pub splash: String,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct QuoteExport {
-    pub quotes: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub title: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub blurb: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub partype: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub info: Option<ChampionInfo>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stats: Option<ChampionStats>,
+    pub quotes: Vec<Quote>,
     pub icon: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub skins: HashMap<String, SkinQuoteExport>,
 }
@@ -0,0 +1,103 @@
+use rand::{rngs::StdRng, thread_rng, Rng, SeedableRng};
+
+use crate::db::Database;
+
+/// Shuffles every quote with a positive weight into a deck holding each of
+/// them once. Heavier quotes are more likely to come up early.
+pub fn order(seed: i64, weights: &[i64]) -> Vec<usize> {
+    let mut rng = StdRng::seed_from_u64(seed as u64);
+
+    // Sorting by u^(1/weight) draws each next card with a probability
+    // proportional to its weight among the cards that are left.
+    let mut keys: Vec<(f64, usize)> = weights
+        .iter()
+        .enumerate()
+        .filter(|(_, weight)| **weight > 0)
+        .map(|(idx, weight)| (rng.gen::<f64>().powf(1.0 / *weight as f64), idx))
+        .collect();
+    keys.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+    keys.into_iter().map(|(_, idx)| idx).collect()
+}
+
+/// Identifies the weights a deck was laid out for, so a deck isn't continued
+/// with different weights, such as in another server.
+pub fn layout(weights: &[i64]) -> i64 {
+    let hash = weights
+        .iter()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, weight| {
+            (hash ^ *weight as u64).wrapping_mul(0x0100_0000_01b3)
+        });
+
+    hash as i64
+}
+
+/// Draws the next quote index from the user's shuffled deck, starting a
+/// freshly shuffled deck once the current one is used up.
+pub async fn draw(
+    database: &Database,
+    user_id: i64,
+    champion: &str,
+    weights: &[i64],
+) -> Result<Option<usize>, sqlx::Error> {
+    let len = weights.iter().filter(|weight| **weight > 0).count() as i64;
+    let layout = layout(weights);
+
+    if len == 0 {
+        return Ok(None);
+    }
+
+    // Another draw can use up or replace the deck between the two steps, in
+    // which case the claim is simply retried.
+    for _ in 0..3 {
+        if let Some((seed, position)) = database
+            .advance_deck(user_id, champion, layout, len)
+            .await?
+        {
+            return Ok(order(seed, weights).get(position as usize).copied());
+        }
+
+        let seed = thread_rng().gen();
+
+        if database
+            .reset_deck(user_id, champion, layout, seed, len)
+            .await?
+        {
+            return Ok(order(seed, weights).first().copied());
+        }
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{layout, order};
+
+    #[test]
+    fn deals_each_weighted_quote_once() {
+        let mut deck = order(42, &[1, 3, 0, 2, 1]);
+        deck.sort_unstable();
+
+        assert_eq!(deck, [0, 1, 3, 4]);
+    }
+
+    #[test]
+    fn same_seed_same_deck() {
+        let weights = [2, 1, 1, 5, 1, 3];
+
+        assert_eq!(order(7, &weights), order(7, &weights));
+        assert_ne!(layout(&weights), layout(&[2, 1, 1, 5, 1, 2]));
+    }
+
+    #[test]
+    fn heavier_quotes_come_first() {
+        let mut first = [0; 2];
+
+        for seed in 0..1000 {
+            first[order(seed, &[1, 9])[0]] += 1;
+        }
+
+        assert!(first[1] > first[0] * 4);
+    }
+}
@@ -0,0 +1,314 @@
+use rand::{prelude::SliceRandom, rngs::StdRng, SeedableRng};
+
+use crate::{
+    champions::CHAMPIONS,
+    ddragon::{ChampionStats, Quote, QuoteCategory},
+};
+
+/// Fights that are still going after this many seconds are decided on the
+/// health left.
+const MAX_SECONDS: f32 = 60.0;
+
+const CRIT_MULTIPLIER: f32 = 1.75;
+
+struct Fighter {
+    name: &'static str,
+    quotes: &'static [Quote],
+    max_hp: f32,
+    hp: f32,
+    attack_damage: f32,
+    armor: f32,
+    attack_speed: f32,
+    crit_chance: f32,
+    // Crits land deterministically once enough crit chance has built up.
+    crit_progress: f32,
+    attacks: u32,
+    next_attack: f32,
+}
+
+impl Fighter {
+    fn new(name: &'static str, stats: &ChampionStats, quotes: &'static [Quote], level: u8) -> Self {
+        let max_hp = ChampionStats::at_level(stats.hp, stats.hpperlevel, level);
+
+        Self {
+            name,
+            quotes,
+            max_hp,
+            hp: max_hp,
+            attack_damage: ChampionStats::at_level(
+                stats.attackdamage,
+                stats.attackdamageperlevel,
+                level,
+            ),
+            armor: ChampionStats::at_level(stats.armor, stats.armorperlevel, level),
+            attack_speed: stats.attack_speed_at_level(level).max(0.1),
+            crit_chance: ChampionStats::at_level(
+                f32::from(stats.crit),
+                f32::from(stats.critperlevel),
+                level,
+            ) / 100.0,
+            crit_progress: 0.0,
+            attacks: 0,
+            next_attack: 0.0,
+        }
+    }
+
+    /// Damage of this fighter's next auto attack against a target with
+    /// `armor`, and whether it crits.
+    fn attack(&mut self, armor: f32) -> (f32, bool) {
+        self.crit_progress += self.crit_chance;
+
+        let crit = self.crit_progress >= 1.0;
+        if crit {
+            self.crit_progress -= 1.0;
+        }
+
+        let multiplier = if crit { CRIT_MULTIPLIER } else { 1.0 };
+        let damage = self.attack_damage * multiplier * 100.0 / (100.0 + armor.max(0.0));
+
+        self.attacks += 1;
+        self.next_attack += 1.0 / self.attack_speed;
+
+        (damage, crit)
+    }
+
+    fn quote(&self, categories: &[QuoteCategory], rng: &mut StdRng) -> Option<&'static str> {
+        let quotes: Vec<&'static Quote> = self
+            .quotes
+            .iter()
+            .filter(|quote| quote.target.is_none() && categories.contains(&quote.category))
+            .collect();
+
+        quotes.choose(rng).map(|quote| quote.text.as_str())
+    }
+}
+
+pub struct Duel {
+    pub lines: Vec<String>,
+    /// 0 if the first champion won, 1 if the second did, `None` for a draw.
+    pub winner: Option<usize>,
+}
+
+/// Pits two champions against each other, trading auto attacks at `level`
+/// until one of them dies. The same matchup at the same level always plays
+/// out the same way.
+pub fn simulate(first: &'static str, second: &'static str, level: u8) -> Option<Duel> {
+    let first_data = CHAMPIONS.get(first)?;
+    let second_data = CHAMPIONS.get(second)?;
+
+    let fighters = [
+        Fighter::new(first, first_data.stats.as_ref()?, &first_data.quotes, level),
+        Fighter::new(
+            second,
+            second_data.stats.as_ref()?,
+            &second_data.quotes,
+            level,
+        ),
+    ];
+
+    let seed = first
+        .bytes()
+        .chain(second.bytes())
+        .fold(u64::from(level), |seed, byte| {
+            seed.wrapping_mul(31).wrapping_add(u64::from(byte))
+        });
+
+    Some(fight(fighters, seed))
+}
+
+fn fight(mut fighters: [Fighter; 2], seed: u64) -> Duel {
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let mut lines = Vec::new();
+
+    for fighter in &fighters {
+        if let Some(quote) = fighter.quote(&[QuoteCategory::Pick, QuoteCategory::Taunt], &mut rng) {
+            lines.push(format!("**{}**: *{}*", fighter.name, quote));
+        }
+    }
+
+    let mut round = 0;
+
+    while fighters.iter().all(|fighter| fighter.hp > 0.0) {
+        // Both attack at once when their attacks line up.
+        let time = fighters[0].next_attack.min(fighters[1].next_attack);
+
+        if time > MAX_SECONDS {
+            break;
+        }
+
+        round += 1;
+
+        let attackers: Vec<usize> = (0..2)
+            .filter(|idx| fighters[*idx].next_attack <= time)
+            .collect();
+
+        let mut hits = Vec::new();
+
+        for attacker in attackers {
+            let defender = 1 - attacker;
+            let armor = fighters[defender].armor;
+            let (damage, crit) = fighters[attacker].attack(armor);
+
+            hits.push((attacker, defender, damage, crit));
+        }
+
+        for (attacker, defender, damage, crit) in hits {
+            fighters[defender].hp -= damage;
+
+            lines.push(format!(
+                "Round {}: {} {} {} for {:.0} ({:.0}/{:.0} left)",
+                round,
+                fighters[attacker].name,
+                if crit { "crits" } else { "hits" },
+                fighters[defender].name,
+                damage,
+                fighters[defender].hp.max(0.0),
+                fighters[defender].max_hp
+            ));
+
+            // Champions speak up on their first swing and on every crit.
+            if fighters[attacker].attacks == 1 || crit {
+                if let Some(quote) = fighters[attacker].quote(&[QuoteCategory::Attack], &mut rng) {
+                    lines.push(format!("**{}**: *{}*", fighters[attacker].name, quote));
+                }
+            }
+        }
+    }
+
+    let winner = match (fighters[0].hp > 0.0, fighters[1].hp > 0.0) {
+        (true, false) => Some(0),
+        (false, true) => Some(1),
+        // Out of time, or both went down to the same swing.
+        _ => {
+            let left: Vec<f32> = fighters
+                .iter()
+                .map(|fighter| fighter.hp / fighter.max_hp)
+                .collect();
+
+            if (left[0] - left[1]).abs() < f32::EPSILON {
+                None
+            } else if left[0] > left[1] {
+                Some(0)
+            } else {
+                Some(1)
+            }
+        }
+    };
+
+    match winner {
+        Some(winner) => {
+            let loser = 1 - winner;
+
+            if let Some(quote) = fighters[loser].quote(&[QuoteCategory::Death], &mut rng) {
+                lines.push(format!("**{}**: *{}*", fighters[loser].name, quote));
+            }
+
+            if let Some(quote) = fighters[winner].quote(&[QuoteCategory::Kill], &mut rng) {
+                lines.push(format!("**{}**: *{}*", fighters[winner].name, quote));
+            }
+
+            lines.push(format!("**{} wins!**", fighters[winner].name));
+        }
+        None => lines.push(String::from("**It's a draw!**")),
+    }
+
+    Duel { lines, winner }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fight, Fighter};
+    use crate::ddragon::{ChampionStats, Quote, QuoteCategory};
+
+    fn stats(hp: f32, attack_damage: f32, crit: u16) -> ChampionStats {
+        ChampionStats {
+            hp,
+            hpperlevel: 90.0,
+            mp: 0.0,
+            mpperlevel: 0.0,
+            movespeed: 340.0,
+            armor: 30.0,
+            armorperlevel: 4.0,
+            spellblock: 30.0,
+            spellblockperlevel: 1.3,
+            attackrange: 125,
+            hpregen: 0.0,
+            hpregenperlevel: 0.0,
+            mpregen: 0.0,
+            mpregenperlevel: 0.0,
+            crit,
+            critperlevel: 0,
+            attackdamage: attack_damage,
+            attackdamageperlevel: 3.0,
+            attackspeedperlevel: 2.5,
+            attackspeed: 0.65,
+        }
+    }
+
+    fn quotes() -> &'static [Quote] {
+        let quote = |text: &str, category| Quote {
+            text: text.to_string(),
+            category,
+            target: None,
+            audio: None,
+        };
+
+        Box::leak(Box::new([
+            quote("Here I come.", QuoteCategory::Pick),
+            quote("Take that.", QuoteCategory::Attack),
+            quote("And stay down.", QuoteCategory::Attack),
+            quote("Too easy.", QuoteCategory::Kill),
+            quote("Not like this.", QuoteCategory::Death),
+        ]))
+    }
+
+    #[test]
+    fn same_seed_same_fight() {
+        let duel = || {
+            fight(
+                [
+                    Fighter::new("Garen", &stats(620.0, 66.0, 25), quotes(), 6),
+                    Fighter::new("Darius", &stats(650.0, 64.0, 25), quotes(), 6),
+                ],
+                7,
+            )
+        };
+
+        let (first, second) = (duel(), duel());
+
+        assert_eq!(first.lines, second.lines);
+        assert_eq!(first.winner, second.winner);
+    }
+
+    #[test]
+    fn stronger_champion_wins() {
+        let duel = fight(
+            [
+                Fighter::new("Garen", &stats(620.0, 30.0, 0), quotes(), 1),
+                Fighter::new("Darius", &stats(650.0, 90.0, 0), quotes(), 1),
+            ],
+            1,
+        );
+
+        assert_eq!(duel.winner, Some(1));
+        assert_eq!(duel.lines.last().unwrap(), "**Darius wins!**");
+        assert!(duel
+            .lines
+            .contains(&String::from("**Garen**: *Not like this.*")));
+    }
+
+    #[test]
+    fn draws_when_time_runs_out() {
+        let duel = fight(
+            [
+                Fighter::new("Garen", &stats(50_000.0, 1.0, 0), &[], 1),
+                Fighter::new("Darius", &stats(50_000.0, 1.0, 0), &[], 1),
+            ],
+            1,
+        );
+
+        assert_eq!(duel.winner, None);
+        assert_eq!(duel.lines.last().unwrap(), "**It's a draw!**");
+    }
+}
@@ -2,9 +2,12 @@
 pub enum Error {
     Http(hyper::http::Error),
     Hyper(hyper::Error),
+    Io(std::io::Error),
+    UnexpectedStatus(hyper::StatusCode),
     Json(simd_json::Error),
     TwilightHttp(twilight_http::Error),
     Deserialization(twilight_http::response::DeserializeBodyError),
+    Interaction(twilight_http::request::application::InteractionError),
 }
 
 impl From<hyper::http::Error> for Error {
@@ -19,6 +22,12 @@ impl From<hyper::Error> for Error {
     }
 }
 
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
 impl From<simd_json::Error> for Error {
     fn from(err: simd_json::Error) -> Self {
         Self::Json(err)
@@ -36,3 +45,9 @@ impl From<twilight_http::response::DeserializeBodyError> for Error {
         Self::Deserialization(err)
     }
 }
+
+impl From<twilight_http::request::application::InteractionError> for Error {
+    fn from(err: twilight_http::request::application::InteractionError) -> Self {
+        Self::Interaction(err)
+    }
+}
@@ -1,7 +1,14 @@
+pub mod audio;
 pub mod champions;
 pub mod db;
+pub mod deck;
 pub mod ddragon;
+pub mod duel;
 pub mod error;
+pub mod matching;
+pub mod qotd;
+pub mod selection;
+pub mod trivia;
 pub mod webhooks;
 pub mod commands;
 
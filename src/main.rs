@@ -1,34 +1,70 @@
 use futures_util::StreamExt;
-use lazy_static::lazy_static;
 use lol_quotes::{
-    champions::CHAMPIONS,
+    audio::AudioCache,
+    champions::QUOTE_INDEX,
     commands::{
-        ChampionsCommand, Context, IamCommand, SetrateCommand, WhoamiCommand, WhoisCommand,
+        context_menus, AnswerCommand, AudioCommand, ChampionCommand, ChampionsCommand,
+        ChaosCommand, ConfigCommand, Context, DuelCommand, EnrollmentCommand, ForgetmeCommand,
+        IamCommand, LeaderboardCommand, LocaleCommand, MydataCommand, OptinCommand, OptoutCommand,
+        QotdCommand, QuoteCommand, SearchCommand, SetrateCommand, SetweightCommand, StatsCommand,
+        TriviaCommand, WhichChampionCommand, WhoamiCommand, WhoisCommand,
     },
-    db::Database,
+    db::{Database, GuildSettings, QuoteTrigger},
+    qotd, selection,
+    trivia::Games,
     webhooks::Webhooks,
 };
-use rand::{prelude::IteratorRandom, thread_rng, Rng};
-use tracing::{error, info};
+use rand::{thread_rng, Rng};
+use tracing::{error, info, warn};
 use twilight_gateway::{
     cluster::{Cluster, ShardScheme},
     Event, Intents,
 };
-use twilight_http::Client;
+use twilight_http::{request::AttachmentFile, Client};
 use twilight_interactions::command::{CommandModel, CreateCommand};
 use twilight_model::{
-    application::interaction::Interaction,
+    application::{command::Command, interaction::Interaction},
+    guild::{PartialMember, Permissions},
     id::{ApplicationId, UserId},
+    user::User,
 };
 
-use std::{env, error::Error, path::Path, sync::Arc};
+use std::{
+    env,
+    error::Error,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// The gateway intents, including the privileged message content intent
+/// that twilight-model 0.8 predates. Replace this with
+/// `Intents::MESSAGE_CONTENT` once twilight is upgraded.
+fn intents() -> Intents {
+    // SAFETY: bitflags only marks this unsafe because bit 15 isn't a flag it
+    // knows about. The bits are sent to the gateway as a plain integer and
+    // Discord defines bit 15 as the message content intent.
+    let message_content = unsafe { Intents::from_bits_unchecked(1 << 15) };
+
+    Intents::GUILD_MESSAGES | message_content
+}
 
-lazy_static! {
-    static ref IGNORED_CHANNELS: Vec<u64> = env::var("IGNORED_CHANNELS")
-        .unwrap_or_default()
-        .split(',')
-        .filter_map(|id_str| id_str.parse().ok())
-        .collect();
+/// The ID, display name and permissions of whoever triggered an interaction.
+fn invoker(
+    member: Option<PartialMember>,
+    user: Option<User>,
+) -> Option<(i64, String, Permissions)> {
+    let (user, nick, permissions) = match member {
+        Some(member) => (
+            member.user,
+            member.nick,
+            member.permissions.unwrap_or_else(Permissions::empty),
+        ),
+        None => (user, None, Permissions::empty()),
+    };
+
+    let user = user?;
+
+    Some((user.id.get() as i64, nick.unwrap_or(user.name), permissions))
 }
 
 #[tokio::main]
@@ -50,7 +86,11 @@ async fn main() -> Result<(), Box<dyn Error + Send + Sync>> {
         return Ok(());
     };
 
-    info!("Done running migrations, booting...");
+    info!("Done running migrations, building quote index");
+
+    lazy_static::initialize(&QUOTE_INDEX);
+
+    info!("Done building quote index, booting...");
 
     let token = match env::var("DISCORD_TOKEN") {
         Ok(token) => token,
@@ -76,25 +116,66 @@ async fn main() -> Result<(), Box<dyn Error + Send + Sync>> {
         }
     };
 
+    // Ignored channels moved into each server's /config.
+    if let Ok(ignored) = env::var("IGNORED_CHANNELS") {
+        warn!(
+            "IGNORED_CHANNELS is no longer read, ignore {} with /config ignore instead",
+            ignored
+        );
+    }
+
     let register_commands = env::var("REGISTER_COMMANDS").is_ok();
 
     let http = Arc::new(Client::new(token.clone()));
     http.set_application_id(ApplicationId::new(application_id).unwrap());
 
     if register_commands {
-        let commands = &[
+        let mut commands: Vec<Command> = vec![
             IamCommand::create_command().into(),
             WhoisCommand::create_command().into(),
             WhoamiCommand::create_command().into(),
             SetrateCommand::create_command().into(),
             ChampionsCommand::create_command().into(),
+            SetweightCommand::create_command().into(),
+            AudioCommand::create_command().into(),
+            ChaosCommand::create_command().into(),
+            LocaleCommand::create_command().into(),
+            QotdCommand::create_command().into(),
+            OptinCommand::create_command().into(),
+            OptoutCommand::create_command().into(),
+            EnrollmentCommand::create_command().into(),
+            MydataCommand::create_command().into(),
+            ForgetmeCommand::create_command().into(),
+            QuoteCommand::create_command().into(),
+            SearchCommand::create_command().into(),
+            ChampionCommand::create_command().into(),
+            StatsCommand::create_command().into(),
+            LeaderboardCommand::create_command().into(),
+            ConfigCommand::create_command().into(),
+            TriviaCommand::create_command().into(),
+            DuelCommand::create_command().into(),
         ];
-        http.set_global_commands(commands)?.exec().await?;
+        commands.extend(context_menus::commands());
+
+        http.set_global_commands(&commands)?.exec().await?;
     }
 
     let webhooks = Arc::new(Webhooks::with_client(http.clone()));
 
-    let (cluster, mut events) = Cluster::builder(token, Intents::GUILD_MESSAGES)
+    let trivia = Arc::new(Games::with_client(http.clone()));
+
+    tokio::spawn(qotd::run(webhooks.clone(), database.clone()));
+
+    let audio_cache_dir = env::var("AUDIO_CACHE_DIR").unwrap_or_else(|_| String::from("audio"));
+    let audio_cache = match AudioCache::new(PathBuf::from(audio_cache_dir)).await {
+        Ok(cache) => Arc::new(cache),
+        Err(e) => {
+            error!("Failed to set up audio cache: {:?}", e);
+            return Ok(());
+        }
+    };
+
+    let (cluster, mut events) = Cluster::builder(token, intents())
         .shard_scheme(ShardScheme::Auto)
         .http_client(http.clone())
         .build()
@@ -113,6 +194,8 @@ async fn main() -> Result<(), Box<dyn Error + Send + Sync>> {
         let http = http.clone();
         let database = database.clone();
         let webhooks = webhooks.clone();
+        let trivia = trivia.clone();
+        let audio_cache = audio_cache.clone();
 
         tokio::spawn(async move {
             if let Event::InteractionCreate(interaction) = event {
@@ -128,25 +211,38 @@ async fn main() -> Result<(), Box<dyn Error + Send + Sync>> {
                     | Interaction::ApplicationCommandAutocomplete(application_command) => {
                         let token = application_command.token;
 
-                        let maybe_user = match application_command.member {
-                            Some(member) => member.user,
-                            None => application_command.user,
-                        };
-                        let user_id = match maybe_user {
-                            Some(user) => user.id.get() as i64,
-                            None => return,
-                        };
+                        let guild_id = application_command.guild_id.map(|id| id.get() as i64);
+
+                        let (user_id, display_name, permissions) =
+                            match invoker(application_command.member, application_command.user) {
+                                Some(invoker) => invoker,
+                                None => return,
+                            };
 
                         let context = Context {
                             http,
                             database,
+                            webhooks,
+                            trivia,
                             user_id,
+                            display_name,
+                            guild_id,
+                            channel_id: application_command.channel_id,
+                            permissions,
                             interaction_id: id,
                             interaction_token: token,
                         };
 
                         match application_command.data.name.as_str() {
                             "iam" => {
+                                let focused = application_command
+                                    .data
+                                    .options
+                                    .iter()
+                                    .find(|option| option.focused)
+                                    .map(|option| option.name.clone())
+                                    .unwrap_or_default();
+
                                 let command = match IamCommand::from_interaction(
                                     application_command.data.into(),
                                 ) {
@@ -155,7 +251,7 @@ async fn main() -> Result<(), Box<dyn Error + Send + Sync>> {
                                 };
 
                                 if is_autocomplete {
-                                    let _ = command.autocomplete(context).await;
+                                    let _ = command.autocomplete(context, &focused).await;
                                 } else {
                                     let _ = command.run(context).await;
                                 }
@@ -200,19 +296,311 @@ async fn main() -> Result<(), Box<dyn Error + Send + Sync>> {
 
                                 let _ = command.run(context).await;
                             }
+                            "setweight" => {
+                                let command = match SetweightCommand::from_interaction(
+                                    application_command.data.into(),
+                                ) {
+                                    Ok(command) => command,
+                                    Err(_) => return,
+                                };
+
+                                let _ = command.run(context).await;
+                            }
+                            "audio" => {
+                                let command = match AudioCommand::from_interaction(
+                                    application_command.data.into(),
+                                ) {
+                                    Ok(command) => command,
+                                    Err(_) => return,
+                                };
+
+                                let _ = command.run(context).await;
+                            }
+                            "chaos" => {
+                                let command = match ChaosCommand::from_interaction(
+                                    application_command.data.into(),
+                                ) {
+                                    Ok(command) => command,
+                                    Err(_) => return,
+                                };
+
+                                let _ = command.run(context).await;
+                            }
+                            "locale" => {
+                                let command = match LocaleCommand::from_interaction(
+                                    application_command.data.into(),
+                                ) {
+                                    Ok(command) => command,
+                                    Err(_) => return,
+                                };
+
+                                let _ = command.run(context).await;
+                            }
+                            "qotd" => {
+                                let command = match QotdCommand::from_interaction(
+                                    application_command.data.into(),
+                                ) {
+                                    Ok(command) => command,
+                                    Err(_) => return,
+                                };
+
+                                let _ = command.run(context).await;
+                            }
+                            "optin" => {
+                                let command = match OptinCommand::from_interaction(
+                                    application_command.data.into(),
+                                ) {
+                                    Ok(command) => command,
+                                    Err(_) => return,
+                                };
+
+                                let _ = command.run(context).await;
+                            }
+                            "optout" => {
+                                let command = match OptoutCommand::from_interaction(
+                                    application_command.data.into(),
+                                ) {
+                                    Ok(command) => command,
+                                    Err(_) => return,
+                                };
+
+                                let _ = command.run(context).await;
+                            }
+                            "enrollment" => {
+                                let command = match EnrollmentCommand::from_interaction(
+                                    application_command.data.into(),
+                                ) {
+                                    Ok(command) => command,
+                                    Err(_) => return,
+                                };
+
+                                let _ = command.run(context).await;
+                            }
+                            "mydata" => {
+                                let command = match MydataCommand::from_interaction(
+                                    application_command.data.into(),
+                                ) {
+                                    Ok(command) => command,
+                                    Err(_) => return,
+                                };
+
+                                let _ = command.run(context).await;
+                            }
+                            "forgetme" => {
+                                let command = match ForgetmeCommand::from_interaction(
+                                    application_command.data.into(),
+                                ) {
+                                    Ok(command) => command,
+                                    Err(_) => return,
+                                };
+
+                                let _ = command.run(context).await;
+                            }
+                            "quote" => {
+                                let command = match QuoteCommand::from_interaction(
+                                    application_command.data.into(),
+                                ) {
+                                    Ok(command) => command,
+                                    Err(_) => return,
+                                };
+
+                                if is_autocomplete {
+                                    let _ = command.autocomplete(context).await;
+                                } else {
+                                    let _ = command.run(context).await;
+                                }
+                            }
+                            "champion" => {
+                                let command = match ChampionCommand::from_interaction(
+                                    application_command.data.into(),
+                                ) {
+                                    Ok(command) => command,
+                                    Err(_) => return,
+                                };
+
+                                if is_autocomplete {
+                                    let _ = command.autocomplete(context).await;
+                                } else {
+                                    let _ = command.run(context).await;
+                                }
+                            }
+                            "stats" => {
+                                let command = match StatsCommand::from_interaction(
+                                    application_command.data.into(),
+                                ) {
+                                    Ok(command) => command,
+                                    Err(_) => return,
+                                };
+
+                                let _ = command.run(context).await;
+                            }
+                            "leaderboard" => {
+                                let command = match LeaderboardCommand::from_interaction(
+                                    application_command.data.into(),
+                                ) {
+                                    Ok(command) => command,
+                                    Err(_) => return,
+                                };
+
+                                let _ = command.run(context).await;
+                            }
+                            "config" => {
+                                let command = match ConfigCommand::from_interaction(
+                                    application_command.data.into(),
+                                ) {
+                                    Ok(command) => command,
+                                    Err(_) => return,
+                                };
+
+                                let _ = command.run(context).await;
+                            }
+                            context_menus::WHICH_CHAMPION => {
+                                if let Some(command) =
+                                    WhichChampionCommand::from_data(application_command.data)
+                                {
+                                    let _ = command.run(context).await;
+                                }
+                            }
+                            context_menus::ANSWER => {
+                                if let Some(command) =
+                                    AnswerCommand::from_data(application_command.data)
+                                {
+                                    let _ = command.run(context).await;
+                                }
+                            }
+                            "trivia" => {
+                                let command = match TriviaCommand::from_interaction(
+                                    application_command.data.into(),
+                                ) {
+                                    Ok(command) => command,
+                                    Err(_) => return,
+                                };
+
+                                if is_autocomplete {
+                                    let _ = command.autocomplete(context).await;
+                                } else {
+                                    let _ = command.run(context).await;
+                                }
+                            }
+                            "duel" => {
+                                let command = match DuelCommand::from_interaction(
+                                    application_command.data.into(),
+                                ) {
+                                    Ok(command) => command,
+                                    Err(_) => return,
+                                };
+
+                                let _ = command.run(context).await;
+                            }
+                            "search" => {
+                                let command = match SearchCommand::from_interaction(
+                                    application_command.data.into(),
+                                ) {
+                                    Ok(command) => command,
+                                    Err(_) => return,
+                                };
+
+                                let _ = command.run(context).await;
+                            }
+                            _ => {}
+                        }
+                    }
+                    Interaction::MessageComponent(component) => {
+                        let component = *component;
+
+                        let (user_id, display_name, permissions) =
+                            match invoker(component.member, component.user) {
+                                Some(invoker) => invoker,
+                                None => return,
+                            };
+
+                        let context = Context {
+                            http,
+                            database,
+                            webhooks,
+                            trivia,
+                            user_id,
+                            display_name,
+                            guild_id: component.guild_id.map(|id| id.get() as i64),
+                            channel_id: component.channel_id,
+                            permissions,
+                            interaction_id: id,
+                            interaction_token: component.token,
+                        };
+
+                        // Custom IDs are "<command>:<arguments>".
+                        match component.data.custom_id.split_once(':') {
+                            Some(("search", arguments)) => {
+                                let _ = SearchCommand::paginate(context, arguments).await;
+                            }
+                            Some(("champions", arguments)) => {
+                                let _ = ChampionsCommand::paginate(
+                                    context,
+                                    arguments,
+                                    &component.data.values,
+                                )
+                                .await;
+                            }
+                            Some(("leaderboard", arguments)) => {
+                                let _ = LeaderboardCommand::switch(context, arguments).await;
+                            }
                             _ => {}
                         }
                     }
                     _ => {}
                 }
             } else if let Event::MessageCreate(message) = event {
-                if !message.author.bot && !IGNORED_CHANNELS.contains(&message.channel_id.get()) {
-                    let (champion, rate) = match database
-                        .get_champion_and_rate(message.author.id.get() as i64)
-                        .await
+                if !message.author.bot {
+                    let user_id = message.author.id.get() as i64;
+
+                    let guild_id = message.guild_id.map(|id| id.get() as i64);
+
+                    let guild_settings = match guild_id {
+                        Some(guild_id) => database
+                            .get_guild_settings(guild_id)
+                            .await
+                            .unwrap_or_default(),
+                        None => GuildSettings::default(),
+                    };
+
+                    if !guild_settings.enabled
+                        || webhooks
+                            .is_ignored(&guild_settings.ignored, message.channel_id)
+                            .await
                     {
-                        Ok(res) => res,
-                        Err(_) => return,
+                        return;
+                    }
+
+                    // Unless the server quotes everyone, only quote people who opted in.
+                    let settings = match database.get_user(user_id).await {
+                        Ok(Some(settings))
+                            if settings.opted_in.unwrap_or(guild_settings.auto_enroll) =>
+                        {
+                            settings
+                        }
+                        Ok(None) if guild_settings.auto_enroll => {
+                            match database.enroll(user_id).await {
+                                Ok(Some(settings)) => settings,
+                                _ => return,
+                            }
+                        }
+                        _ => return,
+                    };
+
+                    if let Some(guild_id) = guild_id {
+                        let _ = database.add_guild_member(guild_id, user_id).await;
+                    }
+
+                    let locale = database
+                        .get_locale(user_id, guild_id)
+                        .await
+                        .unwrap_or_default();
+
+                    let voice = selection::user_voice(&settings, locale);
+
+                    let voice = match voice {
+                        Some(voice) => voice,
+                        None => return,
                     };
 
                     let am_i_mentioned = message
@@ -220,39 +608,111 @@ async fn main() -> Result<(), Box<dyn Error + Send + Sync>> {
                         .iter()
                         .any(|mention| mention.id == bot_user_id);
 
-                    let champion = &CHAMPIONS[&champion];
+                    let odd = thread_rng().gen_range(0..100);
 
-                    let (maybe_quote, odd) = {
-                        let mut rng = thread_rng();
-                        let maybe_quote = champion.quotes.iter().choose(&mut rng);
-                        let odd = rng.gen_range(0..100);
+                    let rate = database
+                        .get_effective_rate(
+                            user_id,
+                            &settings,
+                            guild_id,
+                            message.channel_id.get() as i64,
+                        )
+                        .await
+                        .unwrap_or(settings.rate);
+
+                    if am_i_mentioned || odd < rate {
+                        let referenced_user = message
+                            .referenced_message
+                            .as_ref()
+                            .filter(|referenced| !referenced.author.bot)
+                            .map(|referenced| referenced.author.id)
+                            .or_else(|| {
+                                message
+                                    .mentions
+                                    .iter()
+                                    .find(|mention| !mention.bot && mention.id != message.author.id)
+                                    .map(|mention| mention.id)
+                            });
+
+                        let target = match referenced_user {
+                            Some(id) => database
+                                .get_user(id.get() as i64)
+                                .await
+                                .ok()
+                                .flatten()
+                                .filter(|target| target.opted_in != Some(false))
+                                .map(|target| target.champion),
+                            None => None,
+                        };
 
-                        (maybe_quote, odd)
-                    };
+                        let quote_idx = match selection::pick_quote(
+                            &database,
+                            user_id,
+                            guild_id,
+                            &voice,
+                            &message.content,
+                            target.as_deref(),
+                        )
+                        .await
+                        {
+                            Ok(idx) => idx,
+                            Err(_) => return,
+                        };
+
+                        let quote = match quote_idx.and_then(|idx| voice.quotes.get(idx)) {
+                            Some(quote) => quote,
+                            None => return,
+                        };
 
-                    if am_i_mentioned || odd <= rate {
-                        let webhook =
-                            match webhooks.get_webhook_for_channel(message.channel_id).await {
-                                Ok(webhook) => webhook,
-                                Err(_) => return,
+                        let user_display_name = &message
+                            .member
+                            .as_ref()
+                            .and_then(|member| member.nick.as_ref())
+                            .unwrap_or(&message.author.name);
+
+                        let audio_enabled = settings.audio.unwrap_or(guild_settings.audio);
+
+                        let audio = match &quote.audio {
+                            Some(file) if audio_enabled => audio_cache
+                                .get(voice.locale, file)
+                                .await
+                                .ok()
+                                .map(|data| (file, data)),
+                            _ => None,
+                        };
+
+                        let attachments: Vec<AttachmentFile> = audio
+                            .iter()
+                            .map(|(file, data)| AttachmentFile::from_bytes(file, data))
+                            .collect();
+
+                        if webhooks
+                            .execute(
+                                message.channel_id,
+                                user_display_name,
+                                voice.icon,
+                                &quote.text,
+                                &attachments,
+                            )
+                            .await
+                            .unwrap_or(false)
+                        {
+                            let trigger = if am_i_mentioned {
+                                QuoteTrigger::Mention
+                            } else {
+                                QuoteTrigger::Random
                             };
 
-                        if let Some(token) = webhook.token {
-                            let user_display_name = &message
-                                .member
-                                .as_ref()
-                                .and_then(|member| member.nick.as_ref())
-                                .unwrap_or(&message.author.name);
-
-                            if let Some(quote) = maybe_quote {
-                                let _ = http
-                                    .execute_webhook(webhook.id, &token)
-                                    .content(quote)
-                                    .avatar_url(&champion.icon)
-                                    .username(user_display_name)
-                                    .exec()
-                                    .await;
-                            }
+                            let _ = database
+                                .log_quote(
+                                    user_id,
+                                    guild_id,
+                                    message.channel_id.get() as i64,
+                                    &voice,
+                                    &quote.text,
+                                    trigger,
+                                )
+                                .await;
                         }
                     }
                 }
@@ -0,0 +1,282 @@
+use rand::{prelude::IteratorRandom, thread_rng};
+
+use std::{
+    collections::{HashMap, HashSet},
+    iter,
+};
+
+use crate::{
+    champions::voice_key,
+    ddragon::{Locale, QuoteExport},
+};
+
+const MIN_SCORE: f32 = 1.5;
+
+const STOP_WORDS: &[&str] = &[
+    "a", "about", "after", "again", "all", "also", "am", "an", "and", "any", "are", "as", "at",
+    "be", "been", "before", "being", "but", "by", "can", "could", "did", "do", "does", "doing",
+    "dont", "for", "from", "get", "got", "had", "has", "have", "he", "her", "here", "him", "his",
+    "how", "i", "if", "im", "in", "into", "is", "it", "its", "just", "lol", "me", "more", "my",
+    "no", "not", "now", "of", "off", "oh", "ok", "on", "one", "only", "or", "our", "out", "over",
+    "she", "so", "some", "than", "that", "the", "their", "them", "then", "there", "these", "they",
+    "this", "those", "to", "too", "up", "us", "very", "was", "we", "were", "what", "when", "where",
+    "which", "who", "why", "will", "with", "would", "yes", "you", "your",
+];
+
+const SUFFIXES: &[(&str, &str)] = &[
+    ("ational", "ate"),
+    ("ness", ""),
+    ("ment", ""),
+    ("ingly", ""),
+    ("edly", ""),
+    ("ing", ""),
+    ("ies", "y"),
+    ("ied", "y"),
+    ("ly", ""),
+    ("ed", ""),
+    ("es", ""),
+    ("s", ""),
+];
+
+pub fn stem(word: &str) -> String {
+    let word = word.strip_suffix("'s").unwrap_or(word);
+
+    for (suffix, replacement) in SUFFIXES {
+        if let Some(base) = word.strip_suffix(suffix) {
+            if base.len() >= 3 && !(*suffix == "s" && base.ends_with('s')) {
+                return format!("{}{}", base, replacement);
+            }
+        }
+    }
+
+    word.to_string()
+}
+
+pub fn terms(text: &str) -> HashSet<String> {
+    text.split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .map(|word| word.trim_matches('\'').to_lowercase())
+        .filter(|word| !word.is_empty())
+        .map(|word| stem(&word))
+        .map(|word| word.replace('\'', ""))
+        .filter(|word| word.len() > 1 && !STOP_WORDS.contains(&word.as_str()))
+        .collect()
+}
+
+/// Lowercases `text` and strips everything but letters and digits, so
+/// "Kai'Sa", "kai sa" and "KAISA" all compare equal.
+pub fn normalize(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(a_char != *b_char);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+
+    row[b.len()]
+}
+
+pub struct QuoteIndex {
+    quotes: HashMap<String, Vec<HashSet<String>>>,
+    idf: HashMap<String, f32>,
+}
+
+impl QuoteIndex {
+    pub fn build<'a>(
+        datasets: impl Iterator<Item = (Locale, &'a HashMap<String, QuoteExport>)>,
+    ) -> Self {
+        let mut document_frequency: HashMap<String, usize> = HashMap::new();
+        let mut document_count = 0;
+
+        let mut quotes: HashMap<String, Vec<HashSet<String>>> = HashMap::new();
+
+        for (locale, champions) in datasets {
+            for (name, champion) in champions {
+                let voices = iter::once((voice_key(locale, name, None), &champion.quotes)).chain(
+                    champion
+                        .skins
+                        .iter()
+                        .map(|(skin, data)| (voice_key(locale, name, Some(skin)), &data.quotes)),
+                );
+
+                for (key, voice_quotes) in voices {
+                    let quote_terms: Vec<HashSet<String>> = voice_quotes
+                        .iter()
+                        .map(|quote| terms(&quote.text))
+                        .collect();
+
+                    for term in quote_terms.iter().flatten() {
+                        *document_frequency.entry(term.clone()).or_default() += 1;
+                    }
+
+                    document_count += quote_terms.len();
+
+                    quotes.insert(key, quote_terms);
+                }
+            }
+        }
+
+        let idf = document_frequency
+            .into_iter()
+            .map(|(term, count)| (term, (document_count as f32 / count as f32).ln()))
+            .collect();
+
+        Self { quotes, idf }
+    }
+
+    fn score(&self, message_terms: &HashSet<String>, quote_terms: &HashSet<String>) -> f32 {
+        if quote_terms.is_empty() {
+            return 0.0;
+        }
+
+        let matched: f32 = message_terms
+            .intersection(quote_terms)
+            .filter_map(|term| self.idf.get(term))
+            .sum();
+
+        matched / (quote_terms.len() as f32).sqrt()
+    }
+
+    /// Returns the indices of the voice's quotes containing every one of
+    /// `query_terms`.
+    pub fn containing(&self, voice: &str, query_terms: &HashSet<String>) -> Vec<usize> {
+        self.quotes
+            .get(voice)
+            .map(|quotes| {
+                quotes
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, quote_terms)| query_terms.is_subset(quote_terms))
+                    .map(|(idx, _)| idx)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns the index of the voice's quote that is most relevant to
+    /// `text`, if any scores high enough to be preferred over a random one.
+    pub fn best_match(&self, voice: &str, text: &str) -> Option<usize> {
+        let message_terms = terms(text);
+
+        if message_terms.is_empty() {
+            return None;
+        }
+
+        let scores: Vec<(usize, f32)> = self
+            .quotes
+            .get(voice)?
+            .iter()
+            .map(|quote_terms| self.score(&message_terms, quote_terms))
+            .enumerate()
+            .filter(|(_, score)| *score >= MIN_SCORE)
+            .collect();
+
+        let best = scores
+            .iter()
+            .map(|(_, score)| *score)
+            .fold(f32::NEG_INFINITY, f32::max);
+
+        let mut rng = thread_rng();
+
+        scores
+            .into_iter()
+            .filter(|(_, score)| (best - score).abs() < f32::EPSILON)
+            .map(|(idx, _)| idx)
+            .choose(&mut rng)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use std::collections::HashMap;
+
+    use super::{stem, terms, QuoteIndex};
+    use crate::ddragon::{Locale, QuoteExport};
+
+    fn index(quotes: &[&str]) -> QuoteIndex {
+        let champions: HashMap<String, QuoteExport> = serde_json::from_value(json!({
+            "Nami": { "quotes": quotes, "icon": "" }
+        }))
+        .unwrap();
+
+        QuoteIndex::build([(Locale::English, &champions)].into_iter())
+    }
+
+    #[test]
+    fn stems_common_suffixes() {
+        assert_eq!(stem("flowers"), "flower");
+        assert_eq!(stem("carries"), "carry");
+        assert_eq!(stem("quickly"), "quick");
+        assert_eq!(stem("waves"), "wav");
+        assert_eq!(stem("champion's"), "champion");
+    }
+
+    #[test]
+    fn keeps_short_and_double_s_words() {
+        assert_eq!(stem("has"), "has");
+        assert_eq!(stem("boss"), "boss");
+        assert_eq!(stem("sing"), "sing");
+    }
+
+    #[test]
+    fn drops_stop_words_and_punctuation() {
+        assert!(terms("I am the one and only!").is_empty());
+        assert!(terms("Don't do it, ok?").is_empty());
+
+        let dragon = terms("The DRAGONS are ours...");
+        assert_eq!(dragon.len(), 1);
+        assert!(dragon.contains("dragon"));
+    }
+
+    #[test]
+    fn ranks_rare_terms_first() {
+        let index = index(&[
+            "Beware the dragon.",
+            "The tide turns.",
+            "Sure as the tide.",
+            "Feet are strange.",
+            "People and their air.",
+            "My people are counting on me.",
+            "I will see my home again.",
+            "My quest beckons.",
+            "Whale breath.",
+            "Great. More dry land.",
+            "Swim at your own risk.",
+            "Ride upon the waves.",
+        ]);
+
+        assert_eq!(
+            index.best_match("Nami", "the tide brings a dragon"),
+            Some(0)
+        );
+        assert_eq!(index.best_match("Nami", "whale watching"), Some(8));
+    }
+
+    #[test]
+    fn ignores_weak_matches() {
+        let index = index(&[
+            "The tide will bring the dragon.",
+            "The tide turns.",
+            "Sure as the tide.",
+        ]);
+
+        assert_eq!(index.best_match("Nami", "the tide is high"), None);
+        assert_eq!(index.best_match("Nami", "is it me"), None);
+        assert_eq!(index.best_match("Zed", "the dragon is up"), None);
+    }
+}
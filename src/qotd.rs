@@ -0,0 +1,127 @@
+use rand::{prelude::SliceRandom, rngs::StdRng, SeedableRng};
+use tokio::time;
+use tracing::error;
+use twilight_model::id::ChannelId;
+
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    champions::{self, Voice, CHAMPIONS},
+    db::Database,
+    ddragon::{Locale, Quote},
+    webhooks::Webhooks,
+    Error,
+};
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// The current UTC day, counted from the Unix epoch, and minute of that day.
+pub fn now() -> (i64, i64) {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+
+    (
+        (seconds / SECONDS_PER_DAY) as i64,
+        (seconds % SECONDS_PER_DAY / 60) as i64,
+    )
+}
+
+/// The quote of the day for `day`, which is the same in every channel.
+pub fn pick(day: i64, locale: Locale) -> Option<(&'static str, Voice, &'static Quote)> {
+    let mut rng = StdRng::seed_from_u64(day as u64);
+
+    let mut names: Vec<&'static String> = CHAMPIONS.keys().collect();
+    names.sort();
+
+    let champion = names.choose(&mut rng)?;
+    let voice = champions::voice(champion, None, locale)?;
+
+    let quotes: Vec<&'static Quote> = voice
+        .quotes
+        .iter()
+        .filter(|quote| quote.target.is_none())
+        .collect();
+    let quote = *quotes.choose(&mut rng)?;
+
+    Some((champion, voice, quote))
+}
+
+/// Posts the quote of `day` to a channel. Returns `false` if nothing was
+/// posted.
+pub async fn post(
+    webhooks: &Webhooks,
+    database: &Database,
+    channel_id: ChannelId,
+    guild_id: i64,
+    day: i64,
+) -> Result<bool, Error> {
+    let locale = database
+        .get_guild_settings(guild_id)
+        .await
+        .ok()
+        .and_then(|settings| settings.locale)
+        .unwrap_or_default();
+
+    let (champion, voice, quote) = match pick(day, locale) {
+        Some(qotd) => qotd,
+        None => return Ok(false),
+    };
+
+    webhooks
+        .execute(
+            channel_id,
+            champions::display_name(champion, voice.locale),
+            voice.icon,
+            &format!("**Quote of the day**\n{}", quote.text),
+            &[],
+        )
+        .await
+}
+
+/// Posts every subscribed channel's quote of the day once its time has come.
+pub async fn run(webhooks: Arc<Webhooks>, database: Arc<Database>) {
+    let mut interval = time::interval(Duration::from_secs(60));
+
+    loop {
+        interval.tick().await;
+
+        let (day, minute) = now();
+
+        let due = match database.get_due_qotd(day, minute).await {
+            Ok(due) => due,
+            Err(e) => {
+                error!("Failed to fetch due quotes of the day: {}", e);
+                continue;
+            }
+        };
+
+        for (channel_id, guild_id) in due {
+            // Claim the day before posting, a failed post is better than a
+            // duplicate one after a restart.
+            match database.claim_qotd(channel_id, day).await {
+                Ok(true) => {}
+                Ok(false) => continue,
+                Err(e) => {
+                    error!("Failed to claim quote of the day for {}: {}", channel_id, e);
+                    continue;
+                }
+            }
+
+            let channel = match ChannelId::new(channel_id as u64) {
+                Some(channel) => channel,
+                None => continue,
+            };
+
+            match post(&webhooks, &database, channel, guild_id, day).await {
+                Ok(true) => {}
+                Ok(false) => error!("Can't post the quote of the day in {}", channel_id),
+                Err(e) => error!("Failed to post quote of the day in {}: {:?}", channel_id, e),
+            }
+        }
+    }
+}
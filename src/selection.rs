@@ -0,0 +1,74 @@
+use rand::{prelude::IteratorRandom, thread_rng};
+
+use std::collections::HashMap;
+
+use crate::{
+    champions::{self, Voice, QUOTE_INDEX},
+    db::{Database, UserSettings},
+    ddragon::Locale,
+    deck,
+};
+
+/// Resolves the voice someone is quoted with, a random champion in chaos
+/// mode. Chaos classes that no champion has fall back to the picked
+/// champion rather than quoting nothing.
+pub fn user_voice(settings: &UserSettings, locale: Locale) -> Option<Voice> {
+    let chaos_champion = settings
+        .chaos
+        .then(|| champions::random_champion(&settings.chaos_tags))
+        .flatten();
+
+    match chaos_champion {
+        Some(champion) => champions::voice(champion, None, locale),
+        None => champions::voice(&settings.champion, settings.skin.as_deref(), locale),
+    }
+}
+
+/// Picks the index of the quote `voice` should answer `content` with.
+///
+/// Lines aimed at the `target` champion win over lines matching the message's
+/// keywords, which in turn win over the next line in the user's deck.
+pub async fn pick_quote(
+    database: &Database,
+    user_id: i64,
+    guild_id: Option<i64>,
+    voice: &Voice,
+    content: &str,
+    target: Option<&str>,
+) -> Result<Option<usize>, sqlx::Error> {
+    if let Some(target) = target {
+        let mut rng = thread_rng();
+        let targeted = voice
+            .quotes
+            .iter()
+            .enumerate()
+            .filter(|(_, quote)| quote.target.as_deref() == Some(target))
+            .map(|(idx, _)| idx)
+            .choose(&mut rng);
+
+        if targeted.is_some() {
+            return Ok(targeted);
+        }
+    }
+
+    if let Some(idx) = QUOTE_INDEX.best_match(&voice.key, content) {
+        return Ok(Some(idx));
+    }
+
+    let category_weights = match guild_id {
+        Some(guild_id) => database.get_category_weights(guild_id).await?,
+        None => HashMap::new(),
+    };
+
+    // Interaction lines only make sense when talking to their target.
+    let weights: Vec<i64> = voice
+        .quotes
+        .iter()
+        .map(|quote| match quote.target {
+            Some(_) => 0,
+            None => category_weights.get(&quote.category).copied().unwrap_or(1),
+        })
+        .collect();
+
+    deck::draw(database, user_id, &voice.key, &weights).await
+}
@@ -0,0 +1,192 @@
+use dashmap::{mapref::entry::Entry, DashMap};
+use rand::{prelude::IteratorRandom, thread_rng};
+use tokio::time;
+use twilight_http::Client;
+use twilight_interactions::command::{CommandOption, CreateOption};
+use twilight_model::id::ChannelId;
+
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use crate::{
+    champions::{self, CHAMPIONS},
+    ddragon::{Locale, Quote, QuoteCategory},
+    matching, Error,
+};
+
+/// How long a channel has to guess the champion.
+pub const TIME_LIMIT: Duration = Duration::from_secs(30);
+
+#[derive(Clone, Copy, CommandOption, CreateOption, Debug, Eq, PartialEq)]
+pub enum Difficulty {
+    #[option(name = "Easy: pick, ban and movement lines", value = "easy")]
+    Easy,
+    #[option(name = "Normal: any line", value = "normal")]
+    Normal,
+    #[option(name = "Hard: obscure lines only", value = "hard")]
+    Hard,
+}
+
+impl Difficulty {
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Easy => "Easy",
+            Self::Normal => "Normal",
+            Self::Hard => "Hard",
+        }
+    }
+
+    pub const fn points(self) -> i64 {
+        match self {
+            Self::Easy => 1,
+            Self::Normal => 2,
+            Self::Hard => 3,
+        }
+    }
+
+    // The lines players hear every game are the easy ones.
+    fn allows(self, quote: &Quote) -> bool {
+        let common = quote.target.is_none()
+            && matches!(
+                quote.category,
+                QuoteCategory::Pick | QuoteCategory::Ban | QuoteCategory::Movement
+            );
+
+        match self {
+            Self::Easy => common,
+            Self::Normal => true,
+            Self::Hard => !common,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Game {
+    pub id: u64,
+    pub champion: &'static str,
+    pub quote: &'static Quote,
+    pub difficulty: Difficulty,
+    /// The locale of whoever started the game, for revealing the answer.
+    pub locale: Locale,
+}
+
+pub enum Start {
+    Started(Game),
+    AlreadyRunning,
+    /// No quote fits the difficulty.
+    NoQuote,
+}
+
+pub enum Guess {
+    Correct(Game),
+    Wrong,
+    NoGame,
+}
+
+/// The running trivia games, at most one per channel.
+pub struct Games {
+    client: Arc<Client>,
+    games: DashMap<ChannelId, Game>,
+    next_id: AtomicU64,
+}
+
+impl Games {
+    pub fn with_client(client: Arc<Client>) -> Self {
+        Self {
+            client,
+            games: DashMap::new(),
+            next_id: AtomicU64::new(0),
+        }
+    }
+
+    /// Starts a game in a channel with a random quote that doesn't give away
+    /// its champion.
+    pub fn start(&self, channel_id: ChannelId, difficulty: Difficulty, locale: Locale) -> Start {
+        let entry = match self.games.entry(channel_id) {
+            Entry::Occupied(_) => return Start::AlreadyRunning,
+            Entry::Vacant(entry) => entry,
+        };
+
+        let picked = {
+            let mut rng = thread_rng();
+
+            CHAMPIONS
+                .iter()
+                .flat_map(|(champion, data)| {
+                    let name = matching::normalize(champion);
+
+                    data.quotes
+                        .iter()
+                        .filter(move |quote| !matching::normalize(&quote.text).contains(&name))
+                        .map(move |quote| (champion.as_str(), quote))
+                })
+                .filter(|(_, quote)| difficulty.allows(quote))
+                .choose(&mut rng)
+        };
+
+        let (champion, quote) = match picked {
+            Some(picked) => picked,
+            None => return Start::NoQuote,
+        };
+
+        let game = Game {
+            id: self.next_id.fetch_add(1, Ordering::Relaxed),
+            champion,
+            quote,
+            difficulty,
+            locale,
+        };
+
+        entry.insert(game.clone());
+
+        Start::Started(game)
+    }
+
+    pub fn guess(&self, channel_id: ChannelId, champion: &str) -> Guess {
+        match self
+            .games
+            .remove_if(&channel_id, |_, game| game.champion == champion)
+        {
+            Some((_, game)) => Guess::Correct(game),
+            None if self.games.contains_key(&channel_id) => Guess::Wrong,
+            None => Guess::NoGame,
+        }
+    }
+
+    /// Ends a game once its time is up, unless someone guessed it first, and
+    /// reveals the answer as a followup to the message that started it.
+    pub async fn expire(
+        &self,
+        channel_id: ChannelId,
+        game_id: u64,
+        interaction_token: String,
+    ) -> Result<(), Error> {
+        time::sleep(TIME_LIMIT).await;
+
+        let game = match self
+            .games
+            .remove_if(&channel_id, |_, game| game.id == game_id)
+        {
+            Some((_, game)) => game,
+            None => return Ok(()),
+        };
+
+        let text = format!(
+            "Time's up! It was **{}**.",
+            champions::display_name(game.champion, game.locale)
+        );
+
+        self.client
+            .create_followup_message(&interaction_token)?
+            .content(&text)
+            .exec()
+            .await?;
+
+        Ok(())
+    }
+}
@@ -1,14 +1,26 @@
 use dashmap::DashMap;
-use twilight_http::Client;
-use twilight_model::{channel::Webhook, id::ChannelId};
+use twilight_http::{request::AttachmentFile, response::DeserializeBodyErrorType, Client};
+use twilight_model::{
+    channel::{message::AllowedMentions, Channel, GuildChannel, Webhook},
+    id::ChannelId,
+};
 
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc};
 
 use crate::error::Error;
 
+#[derive(Clone, Copy, Debug)]
+pub struct Route {
+    pub channel_id: ChannelId,
+    pub thread_id: Option<ChannelId>,
+}
+
 pub struct Webhooks {
     client: Arc<Client>,
     cache: DashMap<ChannelId, Webhook>,
+    // `None` for channels that can't be posted in.
+    routes: DashMap<ChannelId, Option<Route>>,
+    categories: DashMap<ChannelId, Option<ChannelId>>,
 }
 
 impl Webhooks {
@@ -16,6 +28,8 @@ impl Webhooks {
         Self {
             client,
             cache: DashMap::new(),
+            routes: DashMap::new(),
+            categories: DashMap::new(),
         }
     }
 
@@ -63,4 +77,172 @@ impl Webhooks {
             }
         }
     }
+
+    /// Fetches a channel, or `None` if it is of a type twilight doesn't know
+    /// yet, such as a forum.
+    async fn fetch_channel(&self, channel_id: ChannelId) -> Result<Option<Channel>, Error> {
+        let response = self.client.channel(channel_id).exec().await?;
+
+        match response.model().await {
+            Ok(channel) => Ok(Some(channel)),
+            Err(e) if matches!(e.kind(), DeserializeBodyErrorType::Deserializing) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Resolves where to post for a channel messages were sent in.
+    ///
+    /// Threads cannot own webhooks, so posts go through their parent
+    /// channel's webhook with the thread ID attached. That parent may be a
+    /// forum, which twilight can't deserialize but which takes webhooks all
+    /// the same. Returns `None` for threads that are archived or locked and
+    /// for channels of an unknown type.
+    pub async fn route(&self, channel_id: ChannelId) -> Result<Option<Route>, Error> {
+        if let Some(route) = self.routes.get(&channel_id) {
+            return Ok(*route);
+        }
+
+        let channel = match self.fetch_channel(channel_id).await? {
+            Some(channel) => channel,
+            None => {
+                self.routes.insert(channel_id, None);
+                return Ok(None);
+            }
+        };
+
+        let thread = match channel {
+            Channel::Guild(GuildChannel::NewsThread(thread)) => {
+                Some((thread.parent_id, thread.thread_metadata))
+            }
+            Channel::Guild(GuildChannel::PrivateThread(thread)) => {
+                Some((thread.parent_id, thread.thread_metadata))
+            }
+            Channel::Guild(GuildChannel::PublicThread(thread)) => {
+                Some((thread.parent_id, thread.thread_metadata))
+            }
+            _ => None,
+        };
+
+        let route = match thread {
+            // Not cached, posting in a thread unarchives it.
+            Some((_, metadata)) if metadata.archived || metadata.locked => return Ok(None),
+            Some((Some(parent_id), _)) => Some(Route {
+                channel_id: parent_id,
+                thread_id: Some(channel_id),
+            }),
+            Some((None, _)) => None,
+            None => Some(Route {
+                channel_id,
+                thread_id: None,
+            }),
+        };
+
+        self.routes.insert(channel_id, route);
+
+        Ok(route)
+    }
+
+    /// Resolves the category a channel, or the channel a thread is in, belongs
+    /// to.
+    pub async fn category(&self, channel_id: ChannelId) -> Result<Option<ChannelId>, Error> {
+        let channel_id = match self.route(channel_id).await? {
+            Some(route) => route.channel_id,
+            None => channel_id,
+        };
+
+        if let Some(category) = self.categories.get(&channel_id) {
+            return Ok(*category);
+        }
+
+        let category = match self.fetch_channel(channel_id).await? {
+            Some(Channel::Guild(GuildChannel::Text(channel))) => channel.parent_id,
+            Some(Channel::Guild(GuildChannel::Voice(channel) | GuildChannel::Stage(channel))) => {
+                channel.parent_id
+            }
+            _ => None,
+        };
+
+        self.categories.insert(channel_id, category);
+
+        Ok(category)
+    }
+
+    /// Whether a channel, the channel a thread is in, or their category is
+    /// in a server's ignored channels.
+    pub async fn is_ignored(&self, ignored: &HashSet<i64>, channel_id: ChannelId) -> bool {
+        if ignored.is_empty() {
+            return false;
+        }
+
+        if ignored.contains(&(channel_id.get() as i64)) {
+            return true;
+        }
+
+        if let Ok(Some(route)) = self.route(channel_id).await {
+            if ignored.contains(&(route.channel_id.get() as i64)) {
+                return true;
+            }
+        }
+
+        matches!(
+            self.category(channel_id).await,
+            Ok(Some(category)) if ignored.contains(&(category.get() as i64))
+        )
+    }
+
+    /// Posts to a channel or thread through its webhook under the given name
+    /// and avatar. Returns `false` if nothing was posted because the channel
+    /// can't be posted in or its webhook has no token.
+    pub async fn execute(
+        &self,
+        channel_id: ChannelId,
+        username: &str,
+        avatar_url: &str,
+        content: &str,
+        attachments: &[AttachmentFile<'_>],
+    ) -> Result<bool, Error> {
+        let route = match self.route(channel_id).await? {
+            Some(route) => route,
+            None => return Ok(false),
+        };
+
+        let webhook = self.get_webhook_for_channel(route.channel_id).await?;
+
+        let token = match webhook.token {
+            Some(token) => token,
+            None => return Ok(false),
+        };
+
+        // Posts repeat what people wrote, which must never ping anyone.
+        let mut request = self
+            .client
+            .execute_webhook(webhook.id, &token)
+            .allowed_mentions(AllowedMentions::default())
+            .content(content)
+            .avatar_url(avatar_url)
+            .username(username)
+            .attach(attachments);
+
+        if let Some(thread_id) = route.thread_id {
+            request = request.thread_id(thread_id);
+        }
+
+        if let Err(e) = request.exec().await {
+            self.invalidate(channel_id);
+            return Err(e.into());
+        }
+
+        Ok(true)
+    }
+
+    /// Forgets everything cached for a channel, e.g. after posting to it failed
+    /// because its webhook was deleted or its thread got archived.
+    pub fn invalidate(&self, channel_id: ChannelId) {
+        if let Some((_, Some(route))) = self.routes.remove(&channel_id) {
+            self.cache.remove(&route.channel_id);
+        }
+
+        self.cache.remove(&channel_id);
+        self.categories.remove(&channel_id);
+    }
 }